@@ -0,0 +1,310 @@
+//! `#[derive(AciObject)]` for [RustyACI](https://docs.rs/rustyaci), see
+//! `rustyaci::AciObject`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, spanned::Spanned, Data, DeriveInput, Expr, Fields, Ident,
+    LitStr, Type, WherePredicate,
+};
+
+/// Generates `Deserialize`, `Serialize` and `AciObject` for a struct holding
+/// the attributes of an ACI class, e.g.
+///
+/// ```ignore
+/// #[derive(Debug, AciObject)]
+/// #[aci(class = "fvBD")]
+/// struct Bd {
+///     dn: Dn,
+///     name: String,
+///     #[aci(rename = "nameAlias")]
+///     name_alias: Option<String>,
+///     #[aci(rename = "unkMacUcastAct", default = String::from("proxy"))]
+///     unknown_unicast: String,
+///     #[aci(default)]
+///     descr: String,
+///     #[aci(children)]
+///     subnets: Vec<Subnet>,
+/// }
+/// ```
+#[proc_macro_derive(AciObject, attributes(aci))]
+pub fn derive_aci_object(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+enum FieldDefault {
+    Default,
+    Expr(Expr),
+}
+
+struct Field {
+    ident: Ident,
+    ty: Type,
+    attribute: String,
+    default: Option<FieldDefault>,
+    children: bool,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let class = class_name(&input)?;
+    let fields = fields(&input)?;
+    let name = &input.ident;
+    let private = quote!(::rustyaci::macros::private);
+
+    let mut deserialize_bounds: Vec<WherePredicate> = Vec::new();
+    let mut serialize_bounds: Vec<WherePredicate> = Vec::new();
+    for field in &fields {
+        let ty = &field.ty;
+        if field.children {
+            deserialize_bounds.push(parse_quote!(#ty: #private::Children));
+            serialize_bounds.push(parse_quote!(#ty: #private::Children));
+        } else {
+            deserialize_bounds.push(parse_quote!(#ty: #private::GetValue));
+            serialize_bounds.push(parse_quote!(#ty: #private::PutValue));
+        }
+    }
+
+    let mut generics = input.generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .extend(deserialize_bounds.iter().chain(&serialize_bounds).cloned());
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (_, _, object_where_clause) = generics.split_for_impl();
+
+    let mut de_generics = input.generics.clone();
+    de_generics.params.insert(0, parse_quote!('de));
+    de_generics
+        .make_where_clause()
+        .predicates
+        .extend(deserialize_bounds);
+    let (de_impl_generics, _, de_where_clause) = de_generics.split_for_impl();
+
+    let mut ser_generics = input.generics.clone();
+    ser_generics
+        .make_where_clause()
+        .predicates
+        .extend(serialize_bounds);
+    let (_, _, ser_where_clause) = ser_generics.split_for_impl();
+
+    let read_fields = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let ty = &field.ty;
+        let attribute = &field.attribute;
+        let get = quote!(<#ty as #private::GetValue>::get_value(object["attributes"].clone(), #attribute));
+        let value = match &field.default {
+            _ if field.children => quote!(<#ty as #private::Children>::get_children(object)),
+            Some(default) => {
+                let default = match default {
+                    FieldDefault::Default => quote!(::core::default::Default::default()),
+                    FieldDefault::Expr(expr) => quote!(#expr),
+                };
+                quote! {
+                    if object["attributes"][#attribute].is_null() {
+                        Ok::<#ty, ::rustyaci::macros::MacroError>(#default)
+                    } else {
+                        #get
+                    }
+                }
+            }
+            None => get,
+        };
+        quote!(#ident: #value.map_err(#private::serde::de::Error::custom)?)
+    });
+
+    let write_fields = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let attribute = &field.attribute;
+        if field.children {
+            quote! {
+                #private::Children::put_children(&self.#ident, &mut children)
+                    .map_err(#private::serde::ser::Error::custom)?;
+            }
+        } else {
+            quote! {
+                if let Some(value) = #private::PutValue::put_value(&self.#ident) {
+                    attributes.insert(#attribute.to_string(), value);
+                }
+            }
+        }
+    });
+
+    let dn = match fields.iter().find(|field| field.attribute == "dn") {
+        Some(field) => {
+            let ident = &field.ident;
+            quote! {
+                #private::PutValue::put_value(&self.#ident)?.as_str()?.parse().ok()
+            }
+        }
+        None => quote!(None),
+    };
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// The ACI class of the object.
+            pub const CLASS_NAME: &'static str = #class;
+        }
+
+        impl #impl_generics #private::AciClass for #name #ty_generics #where_clause {
+            const CLASS_NAME: &'static str = #class;
+        }
+
+        impl #impl_generics ::rustyaci::AciObject for #name #ty_generics #object_where_clause {
+            fn dn(&self) -> Option<::rustyaci::dn::Dn> {
+                #dn
+            }
+        }
+
+        impl #de_impl_generics #private::serde::Deserialize<'de> for #name #ty_generics #de_where_clause {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: #private::serde::Deserializer<'de>,
+            {
+                let value = <#private::serde_json::Value as #private::serde::Deserialize>::deserialize(deserializer)?;
+                let object = &value[#class];
+                if !object.is_object() {
+                    return Err(#private::serde::de::Error::custom(
+                        concat!("expected an object of class ", #class),
+                    ));
+                }
+
+                Ok(#name {
+                    #(#read_fields),*
+                })
+            }
+        }
+
+        impl #impl_generics #private::serde::Serialize for #name #ty_generics #ser_where_clause {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: #private::serde::Serializer,
+            {
+                let mut attributes = #private::serde_json::Map::new();
+                #[allow(unused_mut)]
+                let mut children: Vec<#private::serde_json::Value> = Vec::new();
+                #(#write_fields)*
+
+                let mut object = #private::serde_json::Map::new();
+                object.insert("attributes".to_string(), #private::serde_json::Value::Object(attributes));
+                if !children.is_empty() {
+                    object.insert("children".to_string(), #private::serde_json::Value::Array(children));
+                }
+                let mut value = #private::serde_json::Map::new();
+                value.insert(#class.to_string(), #private::serde_json::Value::Object(object));
+                #private::serde::Serialize::serialize(&value, serializer)
+            }
+        }
+    })
+}
+
+/// The class of `#[aci(class = "..")]`, e.g. `fvBD`.
+fn class_name(input: &DeriveInput) -> syn::Result<LitStr> {
+    let mut class: Option<LitStr> = None;
+    for attribute in input.attrs.iter().filter(|a| a.path().is_ident("aci")) {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("class") {
+                if class.is_some() {
+                    return Err(meta.error("duplicate class"));
+                }
+                class = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unknown aci attribute, expected `class = \"..\"`"))
+            }
+        })?;
+    }
+
+    let Some(class) = class else {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "missing `#[aci(class = \"..\")]`, e.g. `#[aci(class = \"fvBD\")]`",
+        ));
+    };
+    let value = class.value();
+    let mut chars = value.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_alphanumeric());
+    if !valid {
+        return Err(syn::Error::new(
+            class.span(),
+            format!("invalid ACI class name {value:?}"),
+        ));
+    }
+
+    Ok(class)
+}
+
+fn fields(input: &DeriveInput) -> syn::Result<Vec<Field>> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            fields => {
+                return Err(syn::Error::new(
+                    fields.span(),
+                    "AciObject can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "AciObject can only be derived for structs with named fields",
+            ))
+        }
+    };
+
+    fields
+        .iter()
+        .map(|field| {
+            let ident = field.ident.clone().expect("named field");
+            let mut parsed = Field {
+                attribute: ident.to_string(),
+                ident,
+                ty: field.ty.clone(),
+                default: None,
+                children: false,
+            };
+            let mut renamed = false;
+            for attribute in field.attrs.iter().filter(|a| a.path().is_ident("aci")) {
+                attribute.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename") {
+                        if renamed {
+                            return Err(meta.error("duplicate rename"));
+                        }
+                        let name: LitStr = meta.value()?.parse()?;
+                        parsed.attribute = name.value();
+                        renamed = true;
+                    } else if meta.path.is_ident("default") {
+                        if parsed.default.is_some() {
+                            return Err(meta.error("duplicate default"));
+                        }
+                        parsed.default = Some(if meta.input.peek(syn::Token![=]) {
+                            FieldDefault::Expr(meta.value()?.parse()?)
+                        } else {
+                            FieldDefault::Default
+                        });
+                    } else if meta.path.is_ident("children") {
+                        parsed.children = true;
+                    } else {
+                        return Err(meta.error(
+                            "unknown aci attribute, expected `rename = \"..\"`, `default` or `children`",
+                        ));
+                    }
+                    Ok(())
+                })?;
+            }
+            if parsed.children && (renamed || parsed.default.is_some()) {
+                return Err(syn::Error::new(
+                    parsed.ident.span(),
+                    "`children` can't be combined with `rename` or `default`",
+                ));
+            }
+
+            Ok(parsed)
+        })
+        .collect()
+}
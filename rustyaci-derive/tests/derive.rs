@@ -0,0 +1,130 @@
+use rustyaci::{dn::Dn, AciObject};
+use serde_json::json;
+
+#[derive(Debug, PartialEq, AciObject)]
+#[aci(class = "fvSubnet")]
+struct Subnet {
+    ip: String,
+}
+
+#[derive(Debug, AciObject)]
+#[aci(class = "fvBD")]
+struct Bd {
+    dn: Dn,
+    name: String,
+    #[aci(rename = "nameAlias")]
+    name_alias: Option<String>,
+    #[aci(rename = "unkMacUcastAct", default = String::from("proxy"))]
+    unknown_unicast: String,
+    #[aci(default)]
+    descr: String,
+    #[aci(rename = "arpFlood")]
+    arp_flood: bool,
+    #[aci(children)]
+    subnets: Vec<Subnet>,
+}
+
+#[derive(Debug, AciObject)]
+#[aci(class = "eqptIngrBytes5min")]
+struct Counter<T> {
+    #[aci(rename = "unicastCum")]
+    unicast: T,
+}
+
+fn bd() -> serde_json::Value {
+    json!({
+        "fvBD": {
+            "attributes": {
+                "dn": "uni/tn-A/BD-B",
+                "name": "B",
+                "nameAlias": "alias",
+                "arpFlood": "yes"
+            },
+            "children": [
+                {"fvSubnet": {"attributes": {"ip": "10.0.0.1/24"}}},
+                {"fvRsCtx": {"attributes": {"tnFvCtxName": "VRF"}}}
+            ]
+        }
+    })
+}
+
+#[test]
+fn deserialize() {
+    let bd: Bd = serde_json::from_value(bd()).unwrap();
+
+    assert_eq!("uni/tn-A/BD-B", bd.dn.to_string());
+    assert_eq!("B", bd.name);
+    assert_eq!(Some("alias"), bd.name_alias.as_deref());
+    assert_eq!("proxy", bd.unknown_unicast);
+    assert_eq!("", bd.descr);
+    assert!(bd.arp_flood);
+    assert_eq!(
+        vec![Subnet {
+            ip: String::from("10.0.0.1/24")
+        }],
+        bd.subnets
+    );
+}
+
+#[test]
+fn deserialize_errors() {
+    let error =
+        serde_json::from_value::<Bd>(json!({"fvBD": {"attributes": {"dn": "uni/tn-A/BD-B"}}}))
+            .unwrap_err();
+    assert!(error.to_string().contains("Missing Field: name"));
+
+    let error = serde_json::from_value::<Bd>(json!({"fvCtx": {"attributes": {}}})).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("expected an object of class fvBD"));
+}
+
+#[test]
+fn serialize() {
+    let bd: Bd = serde_json::from_value(bd()).unwrap();
+
+    assert_eq!(
+        json!({
+            "fvBD": {
+                "attributes": {
+                    "dn": "uni/tn-A/BD-B",
+                    "name": "B",
+                    "nameAlias": "alias",
+                    "unkMacUcastAct": "proxy",
+                    "descr": "",
+                    "arpFlood": "yes"
+                },
+                "children": [
+                    {"fvSubnet": {"attributes": {"ip": "10.0.0.1/24"}}}
+                ]
+            }
+        }),
+        serde_json::to_value(&bd).unwrap()
+    );
+}
+
+#[test]
+fn class_and_queries() {
+    let bd: Bd = serde_json::from_value(bd()).unwrap();
+
+    assert_eq!("fvBD", Bd::CLASS_NAME);
+    assert_eq!(Some("uni/tn-A/BD-B".parse().unwrap()), bd.dn());
+    assert_eq!("class/fvBD.json", Bd::class_query().build().unwrap());
+    assert_eq!("mo/uni/tn-A/BD-B.json", bd.mo_query().build().unwrap());
+
+    let subnet = Subnet {
+        ip: String::from("10.0.0.1/24"),
+    };
+    assert_eq!(None, subnet.dn());
+    assert!(subnet.mo_query().build().is_err());
+}
+
+#[test]
+fn generics() {
+    let counter: Counter<u64> =
+        serde_json::from_value(json!({"eqptIngrBytes5min": {"attributes": {"unicastCum": "42"}}}))
+            .unwrap();
+
+    assert_eq!(42, counter.unicast);
+    assert_eq!("eqptIngrBytes5min", Counter::<u64>::CLASS_NAME);
+}
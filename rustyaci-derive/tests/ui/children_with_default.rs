@@ -0,0 +1,16 @@
+use rustyaci::AciObject;
+
+#[derive(AciObject)]
+#[aci(class = "fvSubnet")]
+struct Subnet {
+    ip: String,
+}
+
+#[derive(AciObject)]
+#[aci(class = "fvBD")]
+struct Bd {
+    #[aci(children, default)]
+    subnets: Vec<Subnet>,
+}
+
+fn main() {}
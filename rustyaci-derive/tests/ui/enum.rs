@@ -0,0 +1,10 @@
+use rustyaci::AciObject;
+
+#[derive(AciObject)]
+#[aci(class = "fvBD")]
+enum Bd {
+    Flood,
+    Proxy,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use rustyaci::AciObject;
+
+#[derive(AciObject)]
+#[aci(class = "fv BD")]
+struct Bd {
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use rustyaci::AciObject;
+
+#[derive(AciObject)]
+struct Bd {
+    name: String,
+}
+
+fn main() {}
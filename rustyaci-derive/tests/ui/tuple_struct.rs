@@ -0,0 +1,7 @@
+use rustyaci::AciObject;
+
+#[derive(AciObject)]
+#[aci(class = "fvBD")]
+struct Bd(String);
+
+fn main() {}
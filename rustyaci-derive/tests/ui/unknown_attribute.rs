@@ -0,0 +1,10 @@
+use rustyaci::AciObject;
+
+#[derive(AciObject)]
+#[aci(class = "fvBD")]
+struct Bd {
+    #[aci(alias = "nameAlias")]
+    name_alias: String,
+}
+
+fn main() {}
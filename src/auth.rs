@@ -0,0 +1,186 @@
+use openssl::{
+    base64,
+    hash::MessageDigest,
+    pkey::{PKey, Private},
+    sign::Signer,
+};
+
+use std::fmt;
+
+use crate::AciError;
+
+/// The credentials of the user, see [`ACI::connect`](crate::ACI::connect).
+#[derive(Clone)]
+pub enum Credentials {
+    /// Login through `aaaLogin`, the session is refreshed and renewed as
+    /// needed.
+    Password { username: String, password: String },
+    /// Signature based authentication with the private key (PEM) of the X.509
+    /// certificate `certificate_name` of the user. Every request is signed,
+    /// so there is no login and no session token.
+    Certificate {
+        username: String,
+        certificate_name: String,
+        private_key: String,
+    },
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Credentials::Password { username, .. } => f
+                .debug_struct("Password")
+                .field("username", username)
+                .field("password", &"***")
+                .finish(),
+            Credentials::Certificate {
+                username,
+                certificate_name,
+                ..
+            } => f
+                .debug_struct("Certificate")
+                .field("username", username)
+                .field("certificate_name", certificate_name)
+                .field("private_key", &"***")
+                .finish(),
+        }
+    }
+}
+
+/// How the requests to the APIC are authenticated.
+pub(crate) enum Auth {
+    /// Login through `aaaLogin`, the token is sent as `APIC-cookie`.
+    Password { username: String, password: String },
+    /// Every request is signed with the private key of a user certificate.
+    Certificate(Certificate),
+}
+
+impl Auth {
+    pub(crate) fn new(credentials: Credentials) -> Result<Self, AciError> {
+        match credentials {
+            Credentials::Password { username, password } => {
+                Ok(Auth::Password { username, password })
+            }
+            Credentials::Certificate {
+                username,
+                certificate_name,
+                private_key,
+            } => Ok(Auth::Certificate(Certificate::new(
+                username,
+                certificate_name,
+                private_key.as_bytes(),
+            )?)),
+        }
+    }
+}
+
+/// X.509 user certificate used for signature based authentication.
+pub(crate) struct Certificate {
+    username: String,
+    certificate_name: String,
+    key: PKey<Private>,
+}
+
+impl Certificate {
+    pub(crate) fn new(
+        username: String,
+        certificate_name: String,
+        private_key: &[u8],
+    ) -> Result<Self, AciError> {
+        let key = PKey::private_key_from_pem(private_key).map_err(anyhow::Error::from)?;
+
+        Ok(Certificate {
+            username,
+            certificate_name,
+            key,
+        })
+    }
+
+    fn dn(&self) -> String {
+        format!(
+            "uni/userext/user-{}/usercert-{}",
+            self.username, self.certificate_name
+        )
+    }
+
+    /// Signs the method, the path with the query and the body of a request.
+    fn signature(&self, request: &reqwest::Request) -> Result<String, AciError> {
+        let url = request.url();
+        let mut payload = format!("{}{}", request.method(), url.path()).into_bytes();
+        if let Some(query) = url.query() {
+            payload.push(b'?');
+            payload.extend_from_slice(query.as_bytes());
+        }
+        if let Some(body) = request.body().and_then(reqwest::Body::as_bytes) {
+            payload.extend_from_slice(body);
+        }
+
+        let mut signer =
+            Signer::new(MessageDigest::sha256(), &self.key).map_err(anyhow::Error::from)?;
+        signer.update(&payload).map_err(anyhow::Error::from)?;
+        let signature = signer.sign_to_vec().map_err(anyhow::Error::from)?;
+
+        Ok(base64::encode_block(&signature))
+    }
+
+    /// The cookies that authenticate a request.
+    pub(crate) fn cookie(&self, request: &reqwest::Request) -> Result<String, AciError> {
+        Ok(format!(
+            "APIC-Certificate-Algorithm=v1.0; \
+             APIC-Certificate-DN={}; \
+             APIC-Certificate-Fingerprint=fingerprint; \
+             APIC-Request-Signature={}",
+            self.dn(),
+            self.signature(request)?
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use openssl::{rsa::Rsa, sign::Verifier};
+
+    use super::*;
+
+    #[test]
+    fn credentials_debug_hides_secrets() {
+        let credentials = Credentials::Certificate {
+            username: String::from("admin"),
+            certificate_name: String::from("cert"),
+            private_key: String::from("secret"),
+        };
+
+        assert!(!format!("{credentials:?}").contains("secret"));
+    }
+
+    #[test]
+    fn signed_cookie() {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let pem = key.private_key_to_pem_pkcs8().unwrap();
+        let certificate =
+            Certificate::new(String::from("admin"), String::from("cert"), &pem).unwrap();
+
+        let request = reqwest::Client::new()
+            .post("https://SERVER/api/mo/uni.json?rsp-subtree=no")
+            .body("{}")
+            .build()
+            .unwrap();
+        let cookie = certificate.cookie(&request).unwrap();
+
+        let cookies = cookie.split("; ").collect::<Vec<_>>();
+        assert_eq!(cookies[0], "APIC-Certificate-Algorithm=v1.0");
+        assert_eq!(
+            cookies[1],
+            "APIC-Certificate-DN=uni/userext/user-admin/usercert-cert"
+        );
+        assert_eq!(cookies[2], "APIC-Certificate-Fingerprint=fingerprint");
+
+        let signature = cookies[3].strip_prefix("APIC-Request-Signature=").unwrap();
+        let signature = base64::decode_block(signature).unwrap();
+        let mut verifier = Verifier::new(MessageDigest::sha256(), &key).unwrap();
+        verifier
+            .update(b"POST/api/mo/uni.json?rsp-subtree=no{}")
+            .unwrap();
+        assert!(verifier.verify(&signature).unwrap());
+    }
+}
@@ -0,0 +1,68 @@
+//! Generates typed structs for ACI classes from a saved `aci-meta.json`:
+//!
+//! ```text
+//! rustyaci-gen aci-meta.json fvTenant fvCtx fvBD fvSubnet fvRsCtx -o src/models.rs
+//! ```
+//!
+//! Without `-o` the code is written to stdout.
+
+use std::process::ExitCode;
+
+use rustyaci::{codegen, meta::AciMeta};
+
+const USAGE: &str = "Usage: rustyaci-gen <aci-meta.json> <class>... [-o <output.rs>]";
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let mut meta_path = None;
+    let mut output = None;
+    let mut classes = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => match args.next() {
+                Some(path) => output = Some(path),
+                None => return usage(),
+            },
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            _ if meta_path.is_none() => meta_path = Some(arg),
+            _ => classes.push(arg),
+        }
+    }
+    let Some(meta_path) = meta_path else {
+        return usage();
+    };
+    if classes.is_empty() {
+        return usage();
+    }
+
+    let code = AciMeta::from_file(&meta_path).and_then(|meta| {
+        let classes = classes.iter().map(String::as_str).collect::<Vec<_>>();
+        codegen::generate(&meta, &classes)
+    });
+    let code = match code {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match output {
+        Some(path) => {
+            if let Err(e) = std::fs::write(&path, code) {
+                eprintln!("Error: could not write {path}: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print!("{code}"),
+    }
+    ExitCode::SUCCESS
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::FAILURE
+}
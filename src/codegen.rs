@@ -0,0 +1,363 @@
+//! Generates typed structs from the class metadata of the APIC, see the
+//! `rustyaci-gen` binary.
+//!
+//! Each class becomes a `#[derive(AciObject)]` struct named after the class
+//! (`fvBD` becomes `FvBD`) with the `dn`, the naming properties and the
+//! other configurable properties. Enum properties become enums through
+//! [`aci_enum!`](crate::aci_enum), with an `Other(String)` fallback for
+//! values of newer APIC versions. Bitmasks become `Vec<String>`, everything
+//! else, e.g. numbers with named constants, stays a `String`.
+//! Children are added for the contained classes that are generated as well.
+
+use std::collections::BTreeSet;
+
+use crate::meta::{AciMeta, ClassMeta, MetaError, PropertyMeta};
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true", "try",
+    "type", "unsafe", "use", "where", "while", "yield",
+];
+
+/// Generates the source of a module with the structs of `classes`.
+pub fn generate(meta: &AciMeta, classes: &[&str]) -> Result<String, MetaError> {
+    let selected = classes.iter().copied().collect::<BTreeSet<_>>();
+    let mut out = String::from(
+        "// Generated by rustyaci-gen from aci-meta.json, do not edit.\n\n\
+         use rustyaci::{dn::Dn, AciObject};\n",
+    );
+    for class_name in &selected {
+        let class = meta.class(class_name)?;
+        generate_class(&mut out, class_name, class, &selected);
+    }
+
+    Ok(out)
+}
+
+fn generate_class(
+    out: &mut String,
+    class_name: &str,
+    class: &ClassMeta,
+    selected: &BTreeSet<&str>,
+) {
+    let struct_name = type_name(class_name);
+    let naming = class.naming_properties();
+
+    let mut fields = Vec::new();
+    let mut used = BTreeSet::from([String::from("dn")]);
+    for (name, property) in class.configurable_properties() {
+        let ident = unique(field_name(name), &mut used);
+        let values = property.enum_values();
+        let is_naming = naming.contains(&name);
+        let ty = if values.is_empty() || is_naming {
+            property_type(property).to_string()
+        } else {
+            let enum_name = format!("{struct_name}{}", type_name(name));
+            generate_enum(out, &enum_name, class_name, name, &values);
+            enum_name
+        };
+        let ty = if is_naming {
+            ty
+        } else {
+            format!("Option<{ty}>")
+        };
+        fields.push((ident, name, ty, property));
+    }
+    let children = class
+        .contains
+        .iter()
+        .filter(|child| selected.contains(child.as_str()))
+        .map(|child| (unique(field_name(child), &mut used), child))
+        .collect::<Vec<_>>();
+
+    out.push_str(&format!("\n/// {} (`{class_name}`)\n", class.label));
+    out.push_str("#[derive(Debug, Clone, Default, PartialEq, AciObject)]\n");
+    out.push_str(&format!("#[aci(class = {class_name:?})]\n"));
+    out.push_str(&format!("pub struct {struct_name} {{\n"));
+    out.push_str("    pub dn: Option<Dn>,\n");
+    for (ident, name, ty, property) in &fields {
+        if !property.label.is_empty() {
+            out.push_str(&format!("    /// {}\n", property.label));
+        }
+        if ident != name {
+            out.push_str(&format!("    #[aci(rename = {name:?})]\n"));
+        }
+        out.push_str(&format!("    pub {ident}: {ty},\n"));
+    }
+    for (ident, child) in &children {
+        out.push_str("    #[aci(children)]\n");
+        out.push_str(&format!("    pub {ident}: Vec<{}>,\n", type_name(child)));
+    }
+    out.push_str("}\n");
+
+    out.push_str(&format!("\nimpl {struct_name} {{\n"));
+    out.push_str("    /// The format of the relative name.\n");
+    out.push_str(&format!(
+        "    pub const RN_FORMAT: &'static str = {:?};\n",
+        class.rn_format
+    ));
+    out.push_str("    /// The classes of the possible parents.\n");
+    out.push_str(&format!(
+        "    pub const CONTAINED_BY: &'static [&'static str] = &{};\n",
+        str_list(&class.contained_by)
+    ));
+    out.push_str("    /// The classes of the possible children.\n");
+    out.push_str(&format!(
+        "    pub const CONTAINS: &'static [&'static str] = &{};\n",
+        str_list(&class.contains)
+    ));
+    if let Some(relation) = &class.relation_info {
+        out.push_str("    /// The class the relation points to.\n");
+        out.push_str(&format!(
+            "    pub const RELATION_TARGET: &'static str = {:?};\n",
+            relation.to_mo
+        ));
+    }
+
+    // Naming properties that aren't generated can't be filled in
+    let rn_fields = naming
+        .iter()
+        .map(|name| fields.iter().find(|field| field.1 == *name))
+        .collect::<Option<Vec<_>>>();
+    if let Some(rn_fields) = rn_fields {
+        let mut format = class.rn_format.clone();
+        for name in &naming {
+            format = format.replacen(&format!("{{{name}}}"), "{}", 1);
+        }
+        out.push_str("\n    /// The relative name, see [`Self::RN_FORMAT`].\n");
+        out.push_str("    pub fn rn(&self) -> String {\n");
+        if rn_fields.is_empty() {
+            out.push_str(&format!("        String::from({format:?})\n"));
+        } else {
+            // Bitmasks are joined like they are posted
+            let arguments = rn_fields
+                .iter()
+                .map(|field| match field.2.as_str() {
+                    "Vec<String>" => format!("self.{}.join(\",\")", field.0),
+                    _ => format!("self.{}", field.0),
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            out.push_str(&format!("        format!({format:?}, {arguments})\n"));
+        }
+        out.push_str("    }\n");
+    }
+    out.push_str("}\n");
+}
+
+fn generate_enum(out: &mut String, enum_name: &str, class_name: &str, name: &str, values: &[&str]) {
+    out.push_str("\nrustyaci::aci_enum!(\n");
+    out.push_str(&format!("    /// Valid values of `{class_name}.{name}`.\n"));
+    out.push_str(&format!("    {enum_name} {{\n"));
+    let mut used = BTreeSet::new();
+    for value in values {
+        let variant = unique(variant_name(value), &mut used);
+        out.push_str(&format!("        {variant} = {value:?},\n"));
+    }
+    let other = unique(String::from("Other"), &mut used);
+    out.push_str(&format!("        _ => {other},\n"));
+    out.push_str("    }\n);\n");
+}
+
+fn property_type(property: &PropertyMeta) -> &'static str {
+    if property.uitype == "bitmask" {
+        "Vec<String>"
+    } else {
+        "String"
+    }
+}
+
+fn str_list(classes: &BTreeSet<String>) -> String {
+    let classes = classes
+        .iter()
+        .map(|class| format!("{class:?}"))
+        .collect::<Vec<_>>();
+    format!("[{}]", classes.join(", "))
+}
+
+/// Appends `_` until the name isn't taken yet.
+fn unique(mut name: String, used: &mut BTreeSet<String>) -> String {
+    while used.contains(&name) {
+        name.push('_');
+    }
+    used.insert(name.clone());
+    name
+}
+
+/// `fvBD` becomes `FvBD`.
+fn type_name(name: &str) -> String {
+    let mut chars = name.chars().filter(char::is_ascii_alphanumeric);
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + &chars.collect::<String>(),
+        None => String::from("Unknown"),
+    }
+}
+
+/// `tnFvBDName` becomes `tn_fv_bd_name`, keywords get a trailing `_`.
+fn field_name(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut field = String::new();
+    for (index, c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !field.is_empty() && !field.ends_with('_') {
+                field.push('_');
+            }
+            continue;
+        }
+        if c.is_ascii_uppercase() && index > 0 {
+            let previous = chars[index - 1];
+            let next_is_lower = chars.get(index + 1).is_some_and(char::is_ascii_lowercase);
+            if (previous.is_ascii_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_ascii_uppercase() && next_is_lower))
+                && !field.ends_with('_')
+            {
+                field.push('_');
+            }
+        }
+        field.push(c.to_ascii_lowercase());
+    }
+    if field.is_empty() || field.starts_with(|c: char| c.is_ascii_digit()) {
+        field.insert(0, '_');
+    }
+    if KEYWORDS.contains(&field.as_str()) {
+        field.push('_');
+    }
+    field
+}
+
+/// `no-default-gateway` becomes `NoDefaultGateway`, `802.1p` becomes `V8021p`.
+fn variant_name(value: &str) -> String {
+    let mut variant = value
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(type_name)
+        .collect::<String>();
+    if variant.is_empty() {
+        variant = String::from("Empty");
+    } else if variant.starts_with(|c: char| c.is_ascii_digit()) {
+        variant.insert(0, 'V');
+    }
+    variant
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(dead_code)]
+    mod generated {
+        include!("../tests/codegen/tenant.rs");
+    }
+
+    fn meta() -> AciMeta {
+        AciMeta::from_file("tests/json/meta/aci-meta.json").unwrap()
+    }
+
+    const CLASSES: &[&str] = &["fvTenant", "fvCtx", "fvBD", "fvSubnet", "fvRsCtx"];
+
+    #[test]
+    fn generate_matches_snapshot() {
+        let code = generate(&meta(), CLASSES).unwrap();
+        let expected = std::fs::read_to_string("tests/codegen/tenant.rs").unwrap();
+
+        assert_eq!(expected, code);
+    }
+
+    #[test]
+    fn generate_unknown_class() {
+        assert!(matches!(
+            generate(&meta(), &["fvTenant", "fvUnknown"]),
+            Err(MetaError::UnknownClass(_))
+        ));
+    }
+
+    #[test]
+    fn generated_structs() {
+        use generated::*;
+
+        let json = r#"{
+            "fvBD": {
+                "attributes": {"dn": "uni/tn-A/BD-BD1", "name": "BD1", "unkMacUcastAct": "flood", "type": "regular"},
+                "children": [
+                    {"fvSubnet": {"attributes": {"ip": "10.0.0.1/24", "scope": "public,shared"}}},
+                    {"fvRsCtx": {"attributes": {"tnFvCtxName": "VRF1"}}}
+                ]
+            }
+        }"#;
+        let bd: FvBD = serde_json::from_str(json).unwrap();
+
+        assert_eq!(Some(FvBDUnkMacUcastAct::Flood), bd.unk_mac_ucast_act);
+        assert_eq!(Some(FvBDType::Regular), bd.type_);
+        assert_eq!("BD-BD1", bd.rn());
+        assert_eq!("subnet-[10.0.0.1/24]", bd.fv_subnet[0].rn());
+        assert_eq!(
+            Some(vec![String::from("public"), String::from("shared")]),
+            bd.fv_subnet[0].scope
+        );
+        assert_eq!("rsctx", bd.fv_rs_ctx[0].rn());
+        assert_eq!("fvCtx", FvRsCtx::RELATION_TARGET);
+        assert_eq!(&["fvTenant"], FvBD::CONTAINED_BY);
+
+        let value = serde_json::to_value(&bd).unwrap();
+        assert_eq!("flood", value["fvBD"]["attributes"]["unkMacUcastAct"]);
+        assert_eq!("regular", value["fvBD"]["attributes"]["type"]);
+        assert_eq!(
+            "public,shared",
+            value["fvBD"]["children"][1]["fvSubnet"]["attributes"]["scope"]
+        );
+    }
+
+    #[test]
+    fn generated_enum_fallback() {
+        use generated::*;
+
+        let json = r#"{"fvBD": {"attributes": {"name": "BD1", "unkMacUcastAct": "drop"}}}"#;
+        let bd: FvBD = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            Some(FvBDUnkMacUcastAct::Other(String::from("drop"))),
+            bd.unk_mac_ucast_act
+        );
+        let value = serde_json::to_value(&bd).unwrap();
+        assert_eq!("drop", value["fvBD"]["attributes"]["unkMacUcastAct"]);
+    }
+
+    #[test]
+    fn generate_open_values() {
+        let code = generate(&meta(), &["vzEntry"]).unwrap();
+
+        assert!(code.contains("    pub d_from_port: Option<String>,\n"));
+        assert!(code.contains("    pub ether_t: Option<VzEntryEtherT>,\n"));
+        assert!(!code.contains("VzEntryDFromPort"));
+    }
+
+    #[test]
+    fn generate_bitmask_naming_property() {
+        let meta: AciMeta = serde_json::from_value(serde_json::json!({
+            "aciClassMetas": {"fooBar": {
+                "rnFormat": "bar-{flags}",
+                "properties": {"flags": {"isConfigurable": true, "isNaming": true, "uitype": "bitmask"}}
+            }}
+        }))
+        .unwrap();
+        let code = generate(&meta, &["fooBar"]).unwrap();
+
+        assert!(code.contains("    pub flags: Vec<String>,\n"));
+        assert!(code.contains("format!(\"bar-{}\", self.flags.join(\",\"))"));
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!("FvBD", type_name("fvBD"));
+        assert_eq!("tn_fv_bd_name", field_name("tnFvBDName"));
+        assert_eq!("pc_enf_pref", field_name("pcEnfPref"));
+        assert_eq!("fv_rs_ctx", field_name("fvRsCtx"));
+        assert_eq!("type_", field_name("type"));
+        assert_eq!("ip", field_name("ip"));
+        assert_eq!("NoDefaultGateway", variant_name("no-default-gateway"));
+        assert_eq!("V8021p", variant_name("802.1p"));
+        assert_eq!("Empty", variant_name(""));
+    }
+}
@@ -0,0 +1,329 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum DnError {
+    #[error("Empty distinguished name")]
+    Empty,
+    #[error("Empty relative name in {0:?}")]
+    EmptyRn(String),
+    #[error("Unbalanced brackets in {0:?}")]
+    UnbalancedBrackets(String),
+}
+
+/// A relative name, one segment of a [`Dn`] such as `tn-A` or
+/// `pathep-[eth1/1]`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Rn(String);
+
+impl Rn {
+    /// Builds the RN `<prefix>-<value>`. Values containing a `/` are put in
+    /// brackets, e.g. `Rn::new("pathep", "eth1/1")` is `pathep-[eth1/1]`.
+    pub fn new(prefix: &str, value: &str) -> Self {
+        if value.contains('/') && !is_bracketed(value) {
+            Rn(format!("{prefix}-[{value}]"))
+        } else {
+            Rn(format!("{prefix}-{value}"))
+        }
+    }
+
+    /// The prefix that identifies the class of the object, e.g. `tn` for
+    /// `tn-A` or `uni` for `uni`.
+    pub fn prefix(&self) -> &str {
+        match self.0.find(['-', '[']) {
+            Some(index) if self.0[index..].starts_with('-') => &self.0[..index],
+            Some(_) => &self.0,
+            None => &self.0,
+        }
+    }
+
+    /// The naming value after the prefix without brackets, e.g. `eth1/1` for
+    /// `pathep-[eth1/1]`. RNs without a value such as `uni` return `None`.
+    pub fn value(&self) -> Option<&str> {
+        let value = self.0.get(self.prefix().len() + 1..)?;
+        if is_bracketed(value) {
+            Some(&value[1..value.len() - 1])
+        } else {
+            Some(value)
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+fn is_bracketed(value: &str) -> bool {
+    value.starts_with('[') && value.ends_with(']')
+}
+
+impl FromStr for Rn {
+    type Err = DnError;
+
+    fn from_str(rn: &str) -> Result<Self, Self::Err> {
+        let dn: Dn = rn.parse()?;
+        match <[Rn; 1]>::try_from(dn.rns) {
+            Ok([rn]) => Ok(rn),
+            Err(_) => Err(DnError::EmptyRn(rn.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Rn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A distinguished name of a managed object, e.g. `uni/tn-A/ap-B/epg-C`.
+///
+/// ```
+/// use rustyaci::dn::{Dn, Rn};
+///
+/// let dn: Dn = "topology/pod-1/paths-101/pathep-[eth1/1]".parse().unwrap();
+/// assert_eq!(4, dn.rns().len());
+/// assert_eq!(Some("eth1/1"), dn.rn().value());
+/// assert_eq!("topology/pod-1/paths-101", dn.parent().unwrap().to_string());
+///
+/// let epg = Dn::from_rns(["uni", "tn-A", "ap-B"]).unwrap().child(Rn::new("epg", "C"));
+/// assert_eq!("uni/tn-A/ap-B/epg-C", epg.to_string());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Dn {
+    rns: Vec<Rn>,
+}
+
+impl Dn {
+    /// Builds a DN from its RNs, each of which is validated.
+    pub fn from_rns<I>(rns: I) -> Result<Self, DnError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let rns = rns
+            .into_iter()
+            .map(|rn| rn.as_ref().parse())
+            .collect::<Result<Vec<Rn>, _>>()?;
+        if rns.is_empty() {
+            return Err(DnError::Empty);
+        }
+
+        Ok(Dn { rns })
+    }
+
+    pub fn rns(&self) -> &[Rn] {
+        &self.rns
+    }
+
+    /// The last RN, which names the object itself.
+    pub fn rn(&self) -> &Rn {
+        &self.rns[self.rns.len() - 1]
+    }
+
+    /// The class prefix of the object, e.g. `epg` for `uni/tn-A/ap-B/epg-C`.
+    pub fn prefix(&self) -> &str {
+        self.rn().prefix()
+    }
+
+    /// The DN of the parent object, `None` for top level objects like `uni`.
+    pub fn parent(&self) -> Option<Dn> {
+        if self.rns.len() < 2 {
+            return None;
+        }
+        Some(Dn {
+            rns: self.rns[..self.rns.len() - 1].to_vec(),
+        })
+    }
+
+    pub fn child(&self, rn: Rn) -> Dn {
+        let mut rns = self.rns.clone();
+        rns.push(rn);
+        Dn { rns }
+    }
+
+    /// Whether the object is `ancestor` or one of its descendants.
+    pub fn starts_with(&self, ancestor: &Dn) -> bool {
+        self.rns.starts_with(&ancestor.rns)
+    }
+}
+
+impl FromStr for Dn {
+    type Err = DnError;
+
+    /// Splits the DN at the slashes outside of brackets.
+    fn from_str(dn: &str) -> Result<Self, Self::Err> {
+        if dn.is_empty() {
+            return Err(DnError::Empty);
+        }
+
+        let mut rns = Vec::new();
+        let mut depth = 0usize;
+        let mut start = 0;
+        for (index, character) in dn.char_indices() {
+            match character {
+                '[' => depth += 1,
+                ']' => {
+                    depth = depth
+                        .checked_sub(1)
+                        .ok_or_else(|| DnError::UnbalancedBrackets(dn.to_string()))?
+                }
+                '/' if depth == 0 => {
+                    rns.push(parse_rn(dn, &dn[start..index])?);
+                    start = index + 1;
+                }
+                _ => {}
+            }
+        }
+        if depth != 0 {
+            return Err(DnError::UnbalancedBrackets(dn.to_string()));
+        }
+        rns.push(parse_rn(dn, &dn[start..])?);
+
+        Ok(Dn { rns })
+    }
+}
+
+fn parse_rn(dn: &str, rn: &str) -> Result<Rn, DnError> {
+    if rn.is_empty() {
+        return Err(DnError::EmptyRn(dn.to_string()));
+    }
+    Ok(Rn(rn.to_string()))
+}
+
+impl fmt::Display for Dn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, rn) in self.rns.iter().enumerate() {
+            if index > 0 {
+                f.write_str("/")?;
+            }
+            f.write_str(rn.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Dn> for String {
+    fn from(dn: Dn) -> Self {
+        dn.to_string()
+    }
+}
+
+impl From<&Dn> for String {
+    fn from(dn: &Dn) -> Self {
+        dn.to_string()
+    }
+}
+
+impl Serialize for Dn {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Dn {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let dn = String::deserialize(deserializer)?;
+        dn.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dn(dn: &str) -> Dn {
+        dn.parse().unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let epg = dn("uni/tn-A/ap-B/epg-C");
+
+        assert_eq!(
+            vec!["uni", "tn-A", "ap-B", "epg-C"],
+            epg.rns().iter().map(Rn::as_str).collect::<Vec<_>>()
+        );
+        assert_eq!("uni/tn-A/ap-B/epg-C", epg.to_string());
+    }
+
+    #[test]
+    fn parse_brackets() {
+        let path = dn("topology/pod-1/paths-101/pathep-[eth1/1]");
+        assert_eq!(4, path.rns().len());
+        assert_eq!("pathep-[eth1/1]", path.rn().as_str());
+
+        let domain = dn("uni/tn-A/ap-B/epg-C/rsdomAtt-[uni/vmmp-VMware/dom-[DVS/1]]");
+        assert_eq!(5, domain.rns().len());
+        assert_eq!("rsdomAtt", domain.prefix());
+        assert_eq!(Some("uni/vmmp-VMware/dom-[DVS/1]"), domain.rn().value());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(Err(DnError::Empty), "".parse::<Dn>());
+        assert!(matches!(
+            "uni//tn-A".parse::<Dn>(),
+            Err(DnError::EmptyRn(_))
+        ));
+        assert!(matches!(
+            "uni/tn-A/".parse::<Dn>(),
+            Err(DnError::EmptyRn(_))
+        ));
+        assert!(matches!(
+            "topology/pod-1/paths-101/pathep-[eth1/1".parse::<Dn>(),
+            Err(DnError::UnbalancedBrackets(_))
+        ));
+        assert!(matches!(
+            "uni/tn-A]".parse::<Dn>(),
+            Err(DnError::UnbalancedBrackets(_))
+        ));
+    }
+
+    #[test]
+    fn rn_prefix_and_value() {
+        let rn: Rn = "protpaths-101-102".parse().unwrap();
+        assert_eq!("protpaths", rn.prefix());
+        assert_eq!(Some("101-102"), rn.value());
+
+        let rn: Rn = "uni".parse().unwrap();
+        assert_eq!("uni", rn.prefix());
+        assert_eq!(None, rn.value());
+
+        assert!("uni/tn-A".parse::<Rn>().is_err());
+    }
+
+    #[test]
+    fn navigation() {
+        let epg = dn("uni/tn-A/ap-B/epg-C");
+        let tenant = dn("uni/tn-A");
+
+        assert_eq!(Some(dn("uni/tn-A/ap-B")), epg.parent());
+        assert_eq!(None, dn("uni").parent());
+        assert_eq!(
+            epg,
+            tenant.child(Rn::new("ap", "B")).child(Rn::new("epg", "C"))
+        );
+        assert!(epg.starts_with(&tenant));
+        assert!(!tenant.starts_with(&epg));
+        assert!(!dn("uni/tn-AB").starts_with(&tenant));
+    }
+
+    #[test]
+    fn build() {
+        assert_eq!("pathep-[eth1/1]", Rn::new("pathep", "eth1/1").as_str());
+        assert_eq!(dn("uni/tn-A"), Dn::from_rns(["uni", "tn-A"]).unwrap());
+        assert_eq!(Err(DnError::Empty), Dn::from_rns(Vec::<String>::new()));
+        assert!(Dn::from_rns(["uni", "tn-A/ap-B"]).is_err());
+    }
+
+    #[test]
+    fn serde() {
+        let path = dn("topology/pod-1/paths-101/pathep-[eth1/1]");
+        let json = serde_json::to_string(&path).unwrap();
+
+        assert_eq!(r#""topology/pod-1/paths-101/pathep-[eth1/1]""#, json);
+        assert_eq!(path, serde_json::from_str(&json).unwrap());
+        assert!(serde_json::from_str::<Dn>(r#""uni//tn-A""#).is_err());
+    }
+}
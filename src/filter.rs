@@ -0,0 +1,245 @@
+use std::fmt;
+
+use crate::query::{validate_property, QueryError};
+
+/// Filter expression for `query-target-filter` and `rsp-subtree-filter`.
+/// Properties are referenced as `class.property`, e.g. `fvTenant.name`.
+///
+/// The filter renders to the APIC syntax through its `Display` impl, e.g.
+/// `and(eq(fvTenant.name,"x"),wcard(fvTenant.dn,"prod"))`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter {
+    Eq(String, String),
+    Ne(String, String),
+    Lt(String, String),
+    Gt(String, String),
+    Le(String, String),
+    Ge(String, String),
+    Bw(String, String, String),
+    Wcard(String, String),
+    AnyBit(String, String),
+    AllBits(String, String),
+    And(Vec<Filter>),
+    Or(Vec<Filter>),
+    Not(Box<Filter>),
+}
+
+impl Filter {
+    pub fn eq(property: impl Into<String>, value: impl Into<String>) -> Self {
+        Filter::Eq(property.into(), value.into())
+    }
+
+    pub fn ne(property: impl Into<String>, value: impl Into<String>) -> Self {
+        Filter::Ne(property.into(), value.into())
+    }
+
+    pub fn lt(property: impl Into<String>, value: impl Into<String>) -> Self {
+        Filter::Lt(property.into(), value.into())
+    }
+
+    pub fn gt(property: impl Into<String>, value: impl Into<String>) -> Self {
+        Filter::Gt(property.into(), value.into())
+    }
+
+    pub fn le(property: impl Into<String>, value: impl Into<String>) -> Self {
+        Filter::Le(property.into(), value.into())
+    }
+
+    pub fn ge(property: impl Into<String>, value: impl Into<String>) -> Self {
+        Filter::Ge(property.into(), value.into())
+    }
+
+    /// Matches values between `from` and `to`.
+    pub fn bw(property: impl Into<String>, from: impl Into<String>, to: impl Into<String>) -> Self {
+        Filter::Bw(property.into(), from.into(), to.into())
+    }
+
+    /// Matches values containing the given regular expression.
+    pub fn wcard(property: impl Into<String>, value: impl Into<String>) -> Self {
+        Filter::Wcard(property.into(), value.into())
+    }
+
+    pub fn anybit(property: impl Into<String>, value: impl Into<String>) -> Self {
+        Filter::AnyBit(property.into(), value.into())
+    }
+
+    pub fn allbits(property: impl Into<String>, value: impl Into<String>) -> Self {
+        Filter::AllBits(property.into(), value.into())
+    }
+
+    pub fn and(filters: impl IntoIterator<Item = Filter>) -> Self {
+        Filter::And(filters.into_iter().collect())
+    }
+
+    pub fn or(filters: impl IntoIterator<Item = Filter>) -> Self {
+        Filter::Or(filters.into_iter().collect())
+    }
+
+    /// Negates a filter, the same as `!filter`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(filter: Filter) -> Self {
+        Filter::Not(Box::new(filter))
+    }
+
+    /// Checks that all properties are in the form `class.property` and that
+    /// `and` / `or` have at least one operand.
+    pub fn validate(&self) -> Result<(), QueryError> {
+        match self {
+            Filter::Eq(property, _)
+            | Filter::Ne(property, _)
+            | Filter::Lt(property, _)
+            | Filter::Gt(property, _)
+            | Filter::Le(property, _)
+            | Filter::Ge(property, _)
+            | Filter::Bw(property, _, _)
+            | Filter::Wcard(property, _)
+            | Filter::AnyBit(property, _)
+            | Filter::AllBits(property, _) => validate_property(property),
+            Filter::And(filters) | Filter::Or(filters) => {
+                if filters.is_empty() {
+                    return Err(QueryError::InvalidFilter(self.to_string()));
+                }
+                filters.iter().try_for_each(Filter::validate)
+            }
+            Filter::Not(filter) => filter.validate(),
+        }
+    }
+}
+
+impl std::ops::Not for Filter {
+    type Output = Filter;
+
+    fn not(self) -> Self::Output {
+        Filter::Not(Box::new(self))
+    }
+}
+
+/// Quotes a value, escaping backslashes and double quotes inside of it.
+fn quote(value: &str) -> String {
+    let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{value}\"")
+}
+
+fn join(filters: &[Filter]) -> String {
+    filters
+        .iter()
+        .map(Filter::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Filter::Eq(property, value) => write!(f, "eq({property},{})", quote(value)),
+            Filter::Ne(property, value) => write!(f, "ne({property},{})", quote(value)),
+            Filter::Lt(property, value) => write!(f, "lt({property},{})", quote(value)),
+            Filter::Gt(property, value) => write!(f, "gt({property},{})", quote(value)),
+            Filter::Le(property, value) => write!(f, "le({property},{})", quote(value)),
+            Filter::Ge(property, value) => write!(f, "ge({property},{})", quote(value)),
+            Filter::Bw(property, from, to) => {
+                write!(f, "bw({property},{},{})", quote(from), quote(to))
+            }
+            Filter::Wcard(property, value) => write!(f, "wcard({property},{})", quote(value)),
+            Filter::AnyBit(property, value) => write!(f, "anybit({property},{})", quote(value)),
+            Filter::AllBits(property, value) => {
+                write!(f, "allbits({property},{})", quote(value))
+            }
+            Filter::And(filters) => write!(f, "and({})", join(filters)),
+            Filter::Or(filters) => write!(f, "or({})", join(filters)),
+            Filter::Not(filter) => write!(f, "not({filter})"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comparison() {
+        assert_eq!(
+            Filter::eq("fvTenant.name", "x").to_string(),
+            r#"eq(fvTenant.name,"x")"#
+        );
+        assert_eq!(
+            Filter::ne("fvTenant.name", "x").to_string(),
+            r#"ne(fvTenant.name,"x")"#
+        );
+        assert_eq!(
+            Filter::lt("fvTenant.uid", "10").to_string(),
+            r#"lt(fvTenant.uid,"10")"#
+        );
+        assert_eq!(
+            Filter::gt("fvTenant.uid", "10").to_string(),
+            r#"gt(fvTenant.uid,"10")"#
+        );
+        assert_eq!(
+            Filter::le("fvTenant.uid", "10").to_string(),
+            r#"le(fvTenant.uid,"10")"#
+        );
+        assert_eq!(
+            Filter::ge("fvTenant.uid", "10").to_string(),
+            r#"ge(fvTenant.uid,"10")"#
+        );
+        assert_eq!(
+            Filter::bw("fvTenant.uid", "10", "20").to_string(),
+            r#"bw(fvTenant.uid,"10","20")"#
+        );
+        assert_eq!(
+            Filter::wcard("fvTenant.dn", "prod").to_string(),
+            r#"wcard(fvTenant.dn,"prod")"#
+        );
+        assert_eq!(
+            Filter::anybit("fvBD.arpFlood", "1").to_string(),
+            r#"anybit(fvBD.arpFlood,"1")"#
+        );
+        assert_eq!(
+            Filter::allbits("fvBD.arpFlood", "3").to_string(),
+            r#"allbits(fvBD.arpFlood,"3")"#
+        );
+    }
+
+    #[test]
+    fn logical() {
+        let filter = Filter::and([
+            Filter::eq("fvTenant.name", "x"),
+            Filter::wcard("fvTenant.dn", "prod"),
+        ]);
+        assert_eq!(
+            filter.to_string(),
+            r#"and(eq(fvTenant.name,"x"),wcard(fvTenant.dn,"prod"))"#
+        );
+
+        let filter = Filter::or([
+            Filter::not(Filter::eq("fvTenant.name", "common")),
+            !Filter::eq("fvTenant.name", "infra"),
+        ]);
+        assert_eq!(
+            filter.to_string(),
+            r#"or(not(eq(fvTenant.name,"common")),not(eq(fvTenant.name,"infra")))"#
+        );
+    }
+
+    #[test]
+    fn escaping() {
+        let filter = Filter::eq("fvTenant.descr", r#"say "hi" \o/"#);
+        assert_eq!(
+            filter.to_string(),
+            r#"eq(fvTenant.descr,"say \"hi\" \\o/")"#
+        );
+    }
+
+    #[test]
+    fn validate() {
+        assert!(Filter::eq("fvTenant.name", "x").validate().is_ok());
+        assert_eq!(
+            Filter::not(Filter::eq("name", "x")).validate(),
+            Err(QueryError::InvalidProperty(String::from("name")))
+        );
+        assert_eq!(
+            Filter::and([]).validate(),
+            Err(QueryError::InvalidFilter(String::from("and()")))
+        );
+    }
+}
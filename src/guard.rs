@@ -0,0 +1,63 @@
+use std::ops::Deref;
+
+use crate::{AciError, Executor, ACI};
+
+/// Keeps an [`ACI`] session and logs out of the APIC when dropped.
+///
+/// The logout on drop is spawned on the current Tokio runtime. Outside of a
+/// runtime only a warning is logged, so call [`SessionGuard::logout`] if the
+/// session must be closed before the runtime shuts down.
+pub struct SessionGuard<E: Executor + Sync + 'static> {
+    aci: Option<ACI<E>>,
+}
+
+impl<E: Executor + Sync + 'static> SessionGuard<E> {
+    /// Logs out of the APIC and waits for the result.
+    pub async fn logout(mut self) -> Result<(), AciError> {
+        match self.aci.take() {
+            Some(aci) => aci.logout().await,
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the session without logging out.
+    pub fn into_inner(mut self) -> ACI<E> {
+        self.aci.take().unwrap()
+    }
+}
+
+impl<E: Executor + Sync + 'static> Deref for SessionGuard<E> {
+    type Target = ACI<E>;
+
+    fn deref(&self) -> &Self::Target {
+        self.aci.as_ref().unwrap()
+    }
+}
+
+impl<E: Executor + Sync + 'static> Drop for SessionGuard<E> {
+    fn drop(&mut self) {
+        let Some(aci) = self.aci.take() else {
+            return;
+        };
+
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(async move {
+                    if let Err(error) = aci.logout().await {
+                        log::warn!("logout of the APIC failed: {error}");
+                    }
+                });
+            }
+            Err(_) => {
+                log::warn!("could not log out of the APIC, no Tokio runtime is running");
+            }
+        }
+    }
+}
+
+impl<E: Executor + Sync + 'static> ACI<E> {
+    /// Wraps the session in a [`SessionGuard`] that logs out when dropped.
+    pub fn into_guard(self) -> SessionGuard<E> {
+        SessionGuard { aci: Some(self) }
+    }
+}
@@ -1,12 +1,49 @@
-use std::str::FromStr;
-
-use anyhow::{anyhow, Result};
-use reqwest::Client;
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use auth::Auth;
+use client::ClientConfig;
+use config::{AciConfig, ConfigError};
+use dn::Dn;
+use futures::{stream, Stream, TryStreamExt};
+use meta::AciMeta;
+use mo::{ManagedObject, MoStatus};
+use models::Tenant;
+use query::Query;
+use reqwest::{header::HeaderMap, Client, Method, RequestBuilder, StatusCode};
 use serde::{de::DeserializeOwned, Deserialize};
 use serde_json::Value;
 use thiserror::Error;
 
+mod auth;
+pub mod client;
+pub mod codegen;
+pub mod config;
+pub mod dn;
+pub mod filter;
+pub mod guard;
 pub mod macros;
+pub mod meta;
+pub mod mo;
+pub mod models;
+pub mod query;
+pub mod structs;
+pub mod subscription;
+pub mod validate;
+
+pub use auth::Credentials;
+pub use macros::AciObject;
+pub use rustyaci_derive::AciObject;
+
+// The derive refers to `::rustyaci`, also for the models of this crate
+extern crate self as rustyaci;
 
 #[derive(Debug, Error)]
 pub enum AciError {
@@ -16,20 +53,292 @@ pub enum AciError {
     GetError,
     #[error("Post error")]
     PostError,
+    #[error("Refresh error")]
+    RefreshError,
+    #[error("Unexpected response: {0}")]
+    UnexpectedResponse(String),
+    #[error("Object not found (HTTP {status}, code {code}): {text}")]
+    NotFound {
+        status: StatusCode,
+        code: u32,
+        text: String,
+    },
+    #[error("Validation failed (HTTP {status}, code {code}): {text}")]
+    ValidationFailed {
+        status: StatusCode,
+        code: u32,
+        text: String,
+    },
+    #[error("Permission denied (HTTP {status}, code {code}): {text}")]
+    PermissionDenied {
+        status: StatusCode,
+        code: u32,
+        text: String,
+    },
+    #[error("APIC error (HTTP {status}, code {code}): {text}")]
+    Apic {
+        status: StatusCode,
+        code: u32,
+        text: String,
+    },
+    #[error("Site fingerprint mismatch: expected {expected}, got {actual}")]
+    FingerprintMismatch { expected: String, actual: String },
+    #[error("ManagedObject of class {0} has no dn")]
+    MissingDn(String),
+    #[error("Subscriptions need the session token of a password login")]
+    NoSessionToken,
+    #[error("The subscription on {subscribed} is lost, the requests go to {active}")]
+    ServerChanged { subscribed: String, active: String },
+    #[error("Invalid object: {}", format_errors(.0))]
+    Invalid(Vec<validate::ValidationError>),
+    #[error("Invalid configuration: {0}")]
+    Config(#[from] ConfigError),
+    #[error("Invalid query: {0}")]
+    Query(#[from] query::QueryError),
+    #[error("Request error: {0}")]
+    Request(#[from] anyhow::Error),
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("WebSocket error: {0}")]
+    WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
+}
+
+fn format_errors(errors: &[validate::ValidationError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+impl From<tokio_tungstenite::tungstenite::Error> for AciError {
+    fn from(error: tokio_tungstenite::tungstenite::Error) -> Self {
+        AciError::WebSocket(Box::new(error))
+    }
+}
+
+impl AciError {
+    /// Classifies an error returned by the APIC. The APIC reports `102` for
+    /// objects that don't exist and `121`, `122` or `801` for bodies that
+    /// don't match the object model.
+    fn from_apic(status: StatusCode, code: u32, text: String) -> Self {
+        match (status, code) {
+            (StatusCode::NOT_FOUND, _) | (_, 102) => AciError::NotFound { status, code, text },
+            (_, 121 | 122 | 801) => AciError::ValidationFailed { status, code, text },
+            (StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN, _) => {
+                AciError::PermissionDenied { status, code, text }
+            }
+            _ if text.contains("failed validation") => {
+                AciError::ValidationFailed { status, code, text }
+            }
+            _ => AciError::Apic { status, code, text },
+        }
+    }
+
+    /// Whether the APIC could not be reached or failed with a server error,
+    /// in which case another controller of the cluster may still answer.
+    fn is_unavailable(&self) -> bool {
+        match self {
+            AciError::Request(error) => error
+                .downcast_ref::<reqwest::Error>()
+                .is_some_and(|error| error.is_connect() || error.is_timeout()),
+            AciError::Http(error) => error.is_connect() || error.is_timeout(),
+            AciError::NotFound { status, .. }
+            | AciError::ValidationFailed { status, .. }
+            | AciError::PermissionDenied { status, .. }
+            | AciError::Apic { status, .. } => status.is_server_error(),
+            _ => false,
+        }
+    }
+
+    /// Whether the APIC rejected the session token, e.g. because it timed
+    /// out. `PermissionDenied` alone isn't enough, the APIC uses it for
+    /// denied permissions as well.
+    fn is_token_invalid(&self) -> bool {
+        matches!(self, AciError::PermissionDenied { text, .. } if text.contains("Token was invalid"))
+    }
+}
+
+/// The session is renewed this long before the APIC would expire it.
+const SESSION_MARGIN: Duration = Duration::from_secs(30);
+
+/// State of the APIC session as reported by `aaaLogin` / `aaaRefresh`.
+#[derive(Debug)]
+struct Session {
+    token: String,
+    refresh_timeout: Duration,
+    maximum_lifetime: Duration,
+    created: Instant,
+    refreshed: Instant,
+}
+
+impl Session {
+    fn new() -> Self {
+        Session {
+            token: String::new(),
+            refresh_timeout: Duration::ZERO,
+            maximum_lifetime: Duration::ZERO,
+            created: Instant::now(),
+            refreshed: Instant::now(),
+        }
+    }
+
+    /// Once the refresh timeout has passed the APIC has already dropped the
+    /// token, so it can't be refreshed anymore.
+    fn needs_login(&self) -> bool {
+        self.created.elapsed() >= self.maximum_lifetime.saturating_sub(SESSION_MARGIN)
+            || self.refreshed.elapsed() >= self.refresh_timeout
+    }
+
+    fn needs_refresh(&self) -> bool {
+        self.refreshed.elapsed() >= self.refresh_timeout.saturating_sub(SESSION_MARGIN)
+    }
 }
 
+/// The attributes of the `aaaLogin` object returned by login and refresh.
 #[derive(Debug, Deserialize)]
-pub struct AciResponse<T> {
+#[serde(rename_all = "camelCase")]
+struct LoginAttributes {
+    token: String,
+    #[serde(default)]
+    site_fingerprint: Option<String>,
+    refresh_timeout_seconds: String,
+    maximum_lifetime_seconds: String,
+}
+
+impl LoginAttributes {
+    fn from_response(value: &Value) -> Option<Self> {
+        let attributes = value.get("imdata")?.get(0)?["aaaLogin"]["attributes"].clone();
+        serde_json::from_value(attributes).ok()
+    }
+
+    fn refresh_timeout(&self) -> Duration {
+        Duration::from_secs(self.refresh_timeout_seconds.parse().unwrap_or_default())
+    }
+
+    fn maximum_lifetime(&self) -> Duration {
+        Duration::from_secs(self.maximum_lifetime_seconds.parse().unwrap_or_default())
+    }
+}
+
+pub type AciResponse<T> = structs::ImData<T>;
+
+/// A page of a paginated query. Unlike [`AciResponse`], a missing `totalCount`
+/// is kept apart from zero.
+#[derive(Deserialize)]
+struct Page<T> {
+    #[serde(
+        rename = "totalCount",
+        default,
+        deserialize_with = "structs::optional_count"
+    )]
+    total_count: Option<u64>,
     imdata: Vec<T>,
 }
 
+/// The result of a query together with the metadata of the response.
+#[derive(Debug)]
+pub struct QueryResponse<T> {
+    pub items: Vec<T>,
+    pub total_count: u64,
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+}
+
+/// Appends an option to the URI of a query.
+fn with_option(uri: &str, option: &str) -> String {
+    let separator = if uri.contains('?') { '&' } else { '?' };
+    format!("{uri}{separator}{option}")
+}
+
+/// Adds `rsp-subtree-include=count` to the URI of a query, merged into an
+/// existing `rsp-subtree-include` option like `faults`.
+fn count_uri(uri: &str) -> String {
+    let Some((path, query)) = uri.split_once('?') else {
+        return with_option(uri, "rsp-subtree-include=count");
+    };
+    let mut merged = false;
+    let options = query
+        .split('&')
+        .map(|option| match option.strip_prefix("rsp-subtree-include=") {
+            Some(include) => {
+                merged = true;
+                if include.split(',').any(|include| include == "count") {
+                    option.to_string()
+                } else {
+                    format!("{option},count")
+                }
+            }
+            None => option.to_string(),
+        })
+        .collect::<Vec<_>>();
+    if !merged {
+        return with_option(uri, "rsp-subtree-include=count");
+    }
+
+    format!("{path}?{}", options.join("&"))
+}
+
+/// Appends the paging options to the URI of a query.
+fn paged_uri(uri: &str, page: u64, page_size: u32) -> String {
+    with_option(uri, &format!("page={page}&page-size={page_size}"))
+}
+
+/// A connection to an APIC or to the controllers of an APIC cluster, set
+/// up with [`ACI::builder`], [`ACI::connect`] or [`ACI::connect_with_executor`].
+///
+/// The constructors without a [`ClientConfig`], e.g. [`ACI::new`],
+/// [`ACI::new_cluster`] and [`ACI::new_with_certificate`], **accept any
+/// certificate of the APIC** and are only meant for labs.
 pub struct ACI<E: Executor> {
     client: Client,
+    config: ClientConfig,
     executor: E,
-    server: String,
-    username: String,
-    password: String,
-    token: String,
+    servers: Vec<String>,
+    active: AtomicUsize,
+    auth: Auth,
+    session: RwLock<Session>,
+    meta: Option<Arc<AciMeta>>,
+}
+
+/// A response of the APIC whose body has already been read.
+struct Reply {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: String,
+}
+
+impl Reply {
+    /// Decodes the error the APIC returned, either as an `error` object in
+    /// `imdata` or as an unsuccessful HTTP status.
+    fn error(&self) -> Option<AciError> {
+        let body = serde_json::from_str::<Value>(&self.body).unwrap_or_default();
+        let attributes = &body["imdata"][0]["error"]["attributes"];
+        if attributes.is_object() {
+            let code = attributes["code"].as_str().unwrap_or_default();
+            let text = attributes["text"].as_str().unwrap_or_default();
+            return Some(AciError::from_apic(
+                self.status,
+                code.parse().unwrap_or_default(),
+                text.to_string(),
+            ));
+        }
+
+        if !self.status.is_success() {
+            return Some(AciError::from_apic(self.status, 0, self.body.clone()));
+        }
+        None
+    }
+
+    /// The reply itself if it was successful, otherwise the decoded error.
+    fn into_result(self) -> std::result::Result<Self, AciError> {
+        match self.error() {
+            Some(error) => Err(error),
+            None => Ok(self),
+        }
+    }
 }
 
 impl Executor for Client {
@@ -46,120 +355,664 @@ pub trait LocalExecutor {
     async fn execute_request(&self, request: reqwest::Request) -> Result<reqwest::Response>;
 }
 
+/// The settings used by the constructors without a [`ClientConfig`].
+fn default_config() -> ClientConfig {
+    ClientConfig::new().danger_accept_invalid_certs(true)
+}
+
 impl<E: Executor> ACI<E> {
+    fn with_credentials(
+        executor: E,
+        servers: Vec<String>,
+        credentials: Credentials,
+        config: ClientConfig,
+    ) -> std::result::Result<Self, AciError> {
+        if servers.is_empty() {
+            return Err(ConfigError::MissingServer.into());
+        }
+        Ok(ACI {
+            client: config.build()?,
+            auth: Auth::new(credentials)?,
+            config,
+            executor,
+            servers,
+            active: AtomicUsize::new(0),
+            session: RwLock::new(Session::new()),
+            meta: None,
+        })
+    }
+
+    /// Connects to the controllers `servers` of an APIC cluster (or a single
+    /// APIC) with the TLS, timeout and proxy settings of `config`. With a
+    /// password the login is done on the first reachable controller, once it
+    /// fails the requests go to the next one. If `config` pins a site
+    /// fingerprint, the login fails with [`AciError::FingerprintMismatch`] on
+    /// a fabric with another one.
+    pub async fn connect_with_executor(
+        executor: E,
+        servers: Vec<String>,
+        credentials: Credentials,
+        config: ClientConfig,
+    ) -> std::result::Result<Self, AciError> {
+        let aci = ACI::with_credentials(executor, servers, credentials, config)?;
+        aci.with_failover(|| aci.login()).await?;
+
+        Ok(aci)
+    }
+
+    /// Password login without certificate verification, see [`ACI`].
     pub async fn new_with_executor(
         executor: E,
         server: String,
         username: String,
         password: String,
     ) -> std::result::Result<Self, AciError> {
-        let client = Client::builder()
-            .cookie_store(true)
-            .danger_accept_invalid_certs(true);
-        let client = client.build().unwrap();
-        let mut aci = ACI {
-            client,
+        ACI::new_cluster_with_executor(executor, vec![server], username, password).await
+    }
+
+    /// Password login to a cluster without certificate verification, see
+    /// [`ACI`].
+    pub async fn new_cluster_with_executor(
+        executor: E,
+        servers: Vec<String>,
+        username: String,
+        password: String,
+    ) -> std::result::Result<Self, AciError> {
+        ACI::new_cluster_with_executor_and_config(
             executor,
-            server,
+            servers,
             username,
             password,
-            token: String::new(),
+            default_config(),
+        )
+        .await
+    }
+
+    /// Password login, see [`ACI::connect_with_executor`].
+    pub async fn new_cluster_with_executor_and_config(
+        executor: E,
+        servers: Vec<String>,
+        username: String,
+        password: String,
+        config: ClientConfig,
+    ) -> std::result::Result<Self, AciError> {
+        let credentials = Credentials::Password { username, password };
+        ACI::connect_with_executor(executor, servers, credentials, config).await
+    }
+
+    /// Signature based authentication without certificate verification, see
+    /// [`Credentials::Certificate`] and [`ACI`].
+    pub fn new_with_executor_and_certificate(
+        executor: E,
+        server: String,
+        username: String,
+        certificate_name: String,
+        private_key: String,
+    ) -> std::result::Result<Self, AciError> {
+        let credentials = Credentials::Certificate {
+            username,
+            certificate_name,
+            private_key,
         };
-        let result = aci.login().await;
+        ACI::with_credentials(executor, vec![server], credentials, default_config())
+    }
 
-        match result {
-            Ok(()) => Ok(aci),
-            Err(e) => Err(e),
+    /// Checks the objects sent with [`ACI::post_json`] and [`ACI::post_mo`]
+    /// against the class metadata before posting them, see
+    /// [`AciMeta::validate`]. Invalid objects are rejected with
+    /// [`AciError::Invalid`] without contacting the APIC.
+    pub fn with_meta(mut self, meta: impl Into<Arc<AciMeta>>) -> Self {
+        self.meta = Some(meta.into());
+        self
+    }
+
+    fn validate(&self, data: &Value) -> std::result::Result<(), AciError> {
+        let Some(meta) = &self.meta else {
+            return Ok(());
+        };
+        let errors = meta.validate(data);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(AciError::Invalid(errors))
+        }
+    }
+
+    /// The address of the controller the requests are currently sent to.
+    pub fn active_server(&self) -> &str {
+        &self.servers[self.active.load(Ordering::SeqCst)]
+    }
+
+    /// Switches from the controller `from` to the next one of the cluster.
+    /// The session of the old controller is dropped, so the next request
+    /// logs in again.
+    fn fail_over(&self, from: usize) {
+        let next = (from + 1) % self.servers.len();
+        if self
+            .active
+            .compare_exchange(from, next, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+        {
+            *self.session.write().unwrap() = Session::new();
+        }
+    }
+
+    /// Runs `attempt` against the active controller and fails over to the
+    /// next one while the controllers are unavailable, trying each of them
+    /// once.
+    async fn with_failover<T, F, Fut>(&self, attempt: F) -> std::result::Result<T, AciError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = std::result::Result<T, AciError>>,
+    {
+        let mut remaining = self.servers.len();
+        loop {
+            let active = self.active.load(Ordering::SeqCst);
+            match attempt().await {
+                Err(error) if error.is_unavailable() && remaining > 1 => {
+                    remaining -= 1;
+                    self.fail_over(active);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn login(&self) -> std::result::Result<(), AciError> {
+        let Auth::Password { username, password } = &self.auth else {
+            return Ok(());
+        };
+        let json = &serde_json::json!({
+          "aaaUser" : {
+            "attributes" : {
+              "name" : username,
+              "pwd" : password
+            }
+          }
+        });
+        let reply = self
+            .send_once(Method::POST, "aaaLogin.json", Some(json))
+            .await?;
+        if let Some(error) = reply.error() {
+            return Err(error);
+        }
+
+        // Parse the token and the session timeouts out of the response
+        let response = serde_json::from_str::<Value>(&reply.body)?;
+        let Some(login) = LoginAttributes::from_response(&response) else {
+            return Err(AciError::LoginError);
+        };
+        if let Some(expected) = self.config.get_site_fingerprint() {
+            let actual = login.site_fingerprint.as_deref().unwrap_or_default();
+            if actual != expected {
+                return Err(AciError::FingerprintMismatch {
+                    expected: expected.to_string(),
+                    actual: actual.to_string(),
+                });
+            }
         }
+
+        let now = Instant::now();
+        let mut session = self.session.write().unwrap();
+        session.refresh_timeout = login.refresh_timeout();
+        session.maximum_lifetime = login.maximum_lifetime();
+        session.token = login.token;
+        session.created = now;
+        session.refreshed = now;
+
+        Ok(())
     }
 
-    async fn login(&mut self) -> std::result::Result<(), AciError> {
-        let url = format!("https://{}/api/aaaLogin.json", self.server);
-        let request = self.client.post(url);
+    /// Ends the session on the APIC through `aaaLogout` and clears the token.
+    /// A later request logs in again.
+    pub async fn logout(&self) -> std::result::Result<(), AciError> {
+        let Auth::Password { username, .. } = &self.auth else {
+            return Ok(());
+        };
         let json = &serde_json::json!({
           "aaaUser" : {
             "attributes" : {
-              "name" : self.username,
-              "pwd" : self.password
+              "name" : username
             }
           }
         });
-        let request = request.json(json).build().unwrap();
-        let response = self.executor.execute_request(request).await;
+        let reply = self
+            .send_once(Method::POST, "aaaLogout.json", Some(json))
+            .await?;
+        if let Some(error) = reply.error() {
+            return Err(error);
+        }
+
+        *self.session.write().unwrap() = Session::new();
+        Ok(())
+    }
+
+    /// Refreshes the current session token through `aaaRefresh`, which resets
+    /// the refresh timeout of the session but not its maximum lifetime.
+    pub async fn refresh_token(&self) -> std::result::Result<(), AciError> {
+        if let Auth::Certificate(_) = self.auth {
+            return Ok(());
+        }
+        let reply = self.send_once(Method::GET, "aaaRefresh.json", None).await?;
+        if let Some(error) = reply.error() {
+            return Err(error);
+        }
+
+        let response = serde_json::from_str::<Value>(&reply.body)?;
+        let Some(refresh) = LoginAttributes::from_response(&response) else {
+            return Err(AciError::RefreshError);
+        };
+
+        let mut session = self.session.write().unwrap();
+        session.refresh_timeout = refresh.refresh_timeout();
+        session.token = refresh.token;
+        session.refreshed = Instant::now();
+
+        Ok(())
+    }
 
-        // Parse the token out of the response
-        let token = response.unwrap().json::<Value>().await.unwrap();
-        let token = token.get("imdata").unwrap()[0]["aaaLogin"]["attributes"]["token"].as_str();
+    /// Makes sure the session is usable before a request is sent: a new login
+    /// once the maximum lifetime is reached or the refresh timeout has passed,
+    /// otherwise a refresh once the refresh timeout is close. If the APIC
+    /// already dropped the token, the refresh is replaced by a login.
+    async fn ensure_session(&self) -> std::result::Result<(), AciError> {
+        if let Auth::Certificate(_) = self.auth {
+            return Ok(());
+        }
+        let (needs_login, needs_refresh) = {
+            let session = self.session.read().unwrap();
+            (session.needs_login(), session.needs_refresh())
+        };
 
-        match token {
-            Some(token) => {
-                self.token = String::from_str(token).unwrap();
+        if needs_login {
+            self.login().await
+        } else if needs_refresh {
+            match self.refresh_token().await {
+                Err(error) if error.is_token_invalid() => self.login().await,
+                result => result,
             }
-            _ => return Err(AciError::LoginError),
+        } else {
+            Ok(())
         }
+    }
+
+    /// Creates a request against the API of the APIC.
+    fn request(&self, method: Method, uri: &str) -> RequestBuilder {
+        let url = format!("https://{}/api/{}", self.active_server(), uri);
+        self.client.request(method, url)
+    }
+
+    /// Adds the cookie that authenticates the request, either the current
+    /// session token or the signature of the request.
+    fn authenticate(&self, request: &mut reqwest::Request) -> std::result::Result<(), AciError> {
+        let cookie = match &self.auth {
+            Auth::Password { .. } => {
+                format!("APIC-cookie={}", self.session.read().unwrap().token)
+            }
+            Auth::Certificate(certificate) => certificate.cookie(request)?,
+        };
+        let cookie = cookie.parse().map_err(anyhow::Error::from)?;
+        request
+            .headers_mut()
+            .insert(reqwest::header::COOKIE, cookie);
 
         Ok(())
     }
 
-    // async fn refresh_token(&self)
+    /// Sends a request to the APIC. If the APIC answers that the token is
+    /// invalid, either to the refresh of the session or to the request, a new
+    /// login is done and the request is rebuilt and sent once more. Errors
+    /// returned by the APIC are decoded into an [`AciError`]. Unreachable
+    /// controllers are failed over, see [`ACI::new_cluster`].
+    async fn send(
+        &self,
+        method: Method,
+        uri: &str,
+        data: Option<&Value>,
+    ) -> std::result::Result<Reply, AciError> {
+        self.with_failover(|| self.send_to_active(method.clone(), uri, data))
+            .await
+    }
+
+    async fn send_to_active(
+        &self,
+        method: Method,
+        uri: &str,
+        data: Option<&Value>,
+    ) -> std::result::Result<Reply, AciError> {
+        let result = match self.ensure_session().await {
+            Ok(()) => self
+                .send_once(method.clone(), uri, data)
+                .await?
+                .into_result(),
+            Err(error) => Err(error),
+        };
+
+        match result {
+            Err(error)
+                if error.is_token_invalid() && matches!(self.auth, Auth::Password { .. }) =>
+            {
+                self.login().await?;
+                self.send_once(method, uri, data).await?.into_result()
+            }
+            result => result,
+        }
+    }
+
+    async fn send_once(
+        &self,
+        method: Method,
+        uri: &str,
+        data: Option<&Value>,
+    ) -> std::result::Result<Reply, AciError> {
+        let mut request = self.request(method, uri);
+        if let Some(data) = data {
+            request = request.json(data);
+        }
+        let mut request = request.build()?;
+        self.authenticate(&mut request)?;
+        let response = self.executor.execute_request(request).await?;
+
+        Ok(Reply {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: response.text().await?,
+        })
+    }
 
-    async fn get_json_data<T>(&self, uri: String) -> Result<Vec<T>>
+    async fn get_json_data<T>(&self, uri: String) -> std::result::Result<AciResponse<T>, AciError>
     where
         T: DeserializeOwned,
     {
-        let url = format!("https://{}/api/{}", self.server, uri);
-        let request = self.client.get(url).build()?;
-        let response = self.executor.execute_request(request).await?;
-        Ok(response.json::<AciResponse<T>>().await?.imdata)
+        let reply = self.send(Method::GET, &uri, None).await?;
+        Ok(serde_json::from_str::<AciResponse<T>>(&reply.body)?)
+    }
+
+    pub async fn get_json(&self, uri: String) -> std::result::Result<Vec<Value>, AciError> {
+        Ok(self.get_json_data::<Value>(uri).await?.imdata)
+    }
+
+    pub async fn get<T>(&self, uri: String) -> std::result::Result<Vec<T>, AciError>
+    where
+        T: DeserializeOwned,
+    {
+        Ok(self.get_json_data::<T>(uri).await?.imdata)
+    }
+
+    /// Fetches all objects of the class of `T`, e.g.
+    /// `aci.get_class::<models::Epg>()`.
+    pub async fn get_class<T>(&self) -> std::result::Result<Vec<T>, AciError>
+    where
+        T: AciObject,
+    {
+        self.get(T::class_query().build()?).await
+    }
+
+    /// Fetches all tenants, see [`models::Tenant`].
+    pub async fn get_tenants(&self) -> std::result::Result<Vec<Tenant>, AciError> {
+        self.get_class().await
+    }
+
+    /// Like [`ACI::get`], but also returns `totalCount`, the HTTP status and
+    /// the headers of the response.
+    pub async fn get_with_metadata<T>(
+        &self,
+        uri: String,
+    ) -> std::result::Result<QueryResponse<T>, AciError>
+    where
+        T: DeserializeOwned,
+    {
+        let reply = self.send(Method::GET, &uri, None).await?;
+        let response = serde_json::from_str::<AciResponse<T>>(&reply.body)?;
+
+        Ok(QueryResponse {
+            total_count: response.total_count,
+            items: response.imdata,
+            status: reply.status,
+            headers: reply.headers,
+        })
+    }
+
+    pub async fn get_json_with_metadata(
+        &self,
+        uri: String,
+    ) -> std::result::Result<QueryResponse<Value>, AciError> {
+        self.get_with_metadata::<Value>(uri).await
+    }
+
+    /// Counts the objects matched by a query with `rsp-subtree-include=count`,
+    /// without transferring the objects themselves.
+    pub async fn count(&self, uri: String) -> std::result::Result<u64, AciError> {
+        let items = self.get_json(count_uri(&uri)).await?;
+        let Some(count) = items
+            .first()
+            .and_then(|item| item["moCount"]["attributes"]["count"].as_str())
+        else {
+            return Err(AciError::UnexpectedResponse(format!(
+                "no moCount in the response to {uri}"
+            )));
+        };
+
+        count.parse().map_err(|_| {
+            AciError::UnexpectedResponse(format!("invalid moCount {count:?} for {uri}"))
+        })
     }
 
-    pub async fn get_json(&self, uri: String) -> Result<Vec<Value>> {
-        self.get_json_data::<Value>(uri).await
+    /// Fetches the result of a query page by page with `page` and `page-size`
+    /// until `totalCount` objects are received, or without `totalCount` until
+    /// a page comes back short. The query should use a stable order (e.g.
+    /// `order-by`) so the pages don't overlap.
+    pub fn get_paged<T>(
+        &self,
+        uri: String,
+        page_size: u32,
+    ) -> impl Stream<Item = std::result::Result<T, AciError>> + '_
+    where
+        T: DeserializeOwned,
+    {
+        let page_size = page_size.max(1);
+        let pages = stream::try_unfold(Some((0, 0)), move |state| {
+            let uri = uri.clone();
+            async move {
+                let Some((page, fetched)) = state else {
+                    return Ok::<_, AciError>(None);
+                };
+                let reply = self
+                    .send(Method::GET, &paged_uri(&uri, page, page_size), None)
+                    .await?;
+                let response = serde_json::from_str::<Page<T>>(&reply.body)?;
+
+                let received = response.imdata.len() as u64;
+                let fetched = fetched + received;
+                let done = match response.total_count {
+                    Some(total_count) => fetched >= total_count,
+                    None => received < u64::from(page_size),
+                };
+                let next = if received == 0 || done {
+                    None
+                } else {
+                    Some((page + 1, fetched))
+                };
+                Ok(Some((response.imdata, next)))
+            }
+        });
+
+        pages
+            .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+            .try_flatten()
     }
 
-    pub async fn get<T>(&self, uri: String) -> Result<Vec<T>>
+    /// Collects all objects of a paginated query, see [`ACI::get_paged`].
+    pub async fn get_all_paged<T>(
+        &self,
+        uri: String,
+        page_size: u32,
+    ) -> std::result::Result<Vec<T>, AciError>
     where
         T: DeserializeOwned,
     {
-        self.get_json_data::<T>(uri).await
+        self.get_paged(uri, page_size).try_collect().await
     }
 
-    pub async fn post_json(&self, uri: String, data: String) -> Result<()> {
-        let url = format!("https://{}/api/{}", self.server, uri);
+    pub async fn post_json(&self, uri: String, data: String) -> std::result::Result<(), AciError> {
         let data: Value = serde_json::from_str(data.as_str())?;
+        self.validate(&data)?;
 
-        let request = self.client.post(url).json(&data).build()?;
-        let response = self.executor.execute_request(request).await?;
-        if response.json::<Value>().await?.get("imdata").is_some() {
+        self.post_value(&uri, &data).await
+    }
+
+    /// Posts a managed object, including its children, to `mo/<dn>.json`.
+    /// Objects without a `dn` attribute are rejected with
+    /// [`AciError::MissingDn`].
+    pub async fn post_mo(&self, mo: &ManagedObject) -> std::result::Result<(), AciError> {
+        let dn = match mo.get_attribute("dn") {
+            Some(dn) if !dn.is_empty() => dn,
+            _ => return Err(AciError::MissingDn(mo.class_name.clone())),
+        };
+        let uri = Query::mo(dn).build()?;
+        let data = serde_json::to_value(mo)?;
+        self.validate(&data)?;
+
+        self.post_value(&uri, &data).await
+    }
+
+    /// Deletes the object `dn` and its children through an HTTP `DELETE`.
+    /// The APIC reports objects that don't exist as [`AciError::NotFound`].
+    pub async fn delete(&self, dn: &Dn) -> std::result::Result<(), AciError> {
+        let uri = Query::mo(dn).build()?;
+        self.send(Method::DELETE, &uri, None).await?;
+
+        Ok(())
+    }
+
+    /// Deletes the children of `parent` in one transaction by posting them
+    /// with `status: deleted`. Either all of them are deleted or, if the
+    /// APIC rejects one, none.
+    pub async fn delete_children(
+        &self,
+        parent: &ManagedObject,
+    ) -> std::result::Result<(), AciError> {
+        let mut parent = parent.clone();
+        for child in &mut parent.children {
+            child.status = Some(MoStatus::Deleted);
+        }
+
+        self.post_mo(&parent).await
+    }
+
+    async fn post_value(&self, uri: &str, data: &Value) -> std::result::Result<(), AciError> {
+        let reply = self.send(Method::POST, uri, Some(data)).await?;
+        if serde_json::from_str::<Value>(&reply.body)?
+            .get("imdata")
+            .is_some()
+        {
             return Ok(());
         }
-        Err(anyhow!("Error!"))
+        Err(AciError::PostError)
     }
 
     // This function creates a snapshot of the ACI fabric
-    pub async fn snapshot(&self, description: Option<String>, dn: Option<String>) -> Result<()> {
+    pub async fn snapshot(
+        &self,
+        description: Option<String>,
+        dn: Option<String>,
+    ) -> std::result::Result<(), AciError> {
         let json = get_snapshot_data(description, dn);
 
         self.post_json(String::from("mo.json"), json.to_string())
             .await
     }
 
-    pub fn get_token(&self) -> &String {
-        &self.token
+    /// Creates a snapshot of the subtree below `dn`, see [`ACI::snapshot`].
+    pub async fn snapshot_of(
+        &self,
+        description: Option<String>,
+        dn: &Dn,
+    ) -> std::result::Result<(), AciError> {
+        self.snapshot(description, Some(dn.into())).await
+    }
+
+    pub fn get_token(&self) -> String {
+        self.session.read().unwrap().token.clone()
     }
 }
 
 impl ACI<Client> {
+    /// Starts the configuration of a connection, see [`AciConfig`].
+    pub fn builder() -> AciConfig {
+        AciConfig::new()
+    }
+
+    /// See [`ACI::connect_with_executor`].
+    pub async fn connect(
+        servers: Vec<String>,
+        credentials: Credentials,
+        config: ClientConfig,
+    ) -> std::result::Result<Self, AciError> {
+        let executor = config.build()?;
+        ACI::connect_with_executor(executor, servers, credentials, config).await
+    }
+
+    /// Password login without certificate verification, see [`ACI`].
     pub async fn new(
         server: String,
         username: String,
         password: String,
     ) -> std::result::Result<Self, AciError> {
-        let executor = Client::builder()
-            .cookie_store(true)
-            .danger_accept_invalid_certs(true);
-        let executor = executor.build().unwrap();
-        ACI::new_with_executor(executor, server, username, password).await
+        ACI::new_cluster(vec![server], username, password).await
+    }
+
+    /// Password login, see [`ACI::connect`].
+    pub async fn new_with_config(
+        server: String,
+        username: String,
+        password: String,
+        config: ClientConfig,
+    ) -> std::result::Result<Self, AciError> {
+        ACI::new_cluster_with_config(vec![server], username, password, config).await
+    }
+
+    /// Password login to the controllers of a cluster, e.g.
+    /// `["apic1", "apic2", "apic3"]`, without certificate verification, see
+    /// [`ACI`].
+    pub async fn new_cluster(
+        servers: Vec<String>,
+        username: String,
+        password: String,
+    ) -> std::result::Result<Self, AciError> {
+        ACI::new_cluster_with_config(servers, username, password, default_config()).await
+    }
+
+    /// Password login to a cluster, see [`ACI::connect`].
+    pub async fn new_cluster_with_config(
+        servers: Vec<String>,
+        username: String,
+        password: String,
+        config: ClientConfig,
+    ) -> std::result::Result<Self, AciError> {
+        ACI::connect(
+            servers,
+            Credentials::Password { username, password },
+            config,
+        )
+        .await
+    }
+
+    /// Signature based authentication without certificate verification, see
+    /// [`Credentials::Certificate`] and [`ACI`].
+    pub fn new_with_certificate(
+        server: String,
+        username: String,
+        certificate_name: String,
+        private_key: String,
+    ) -> std::result::Result<Self, AciError> {
+        ACI::new_with_executor_and_certificate(
+            default_config().build()?,
+            server,
+            username,
+            certificate_name,
+            private_key,
+        )
     }
 }
 
@@ -192,24 +1045,144 @@ fn get_snapshot_data(description: Option<String>, dn: Option<String>) -> Value {
 #[cfg(test)]
 mod tests {
     use core::panic;
-    use std::{fs, str::FromStr};
+    use std::{
+        collections::HashMap,
+        fs,
+        str::FromStr,
+        sync::{
+            atomic::{AtomicBool, AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        time::Duration,
+    };
 
     use anyhow::anyhow;
+    use futures::{SinkExt, StreamExt, TryStreamExt};
+    use openssl::{hash::MessageDigest, pkey::PKey, rsa::Rsa, sign::Verifier};
     use serde::Deserialize;
     use serde_json::Value;
-
-    use crate::{get_snapshot_data, Executor, ACI};
-    pub struct MockClient;
+    use tokio_tungstenite::tungstenite::Message;
+
+    use reqwest::StatusCode;
+
+    use crate::{
+        client::ClientConfig,
+        config::ConfigError,
+        count_uri, get_snapshot_data,
+        mo::ManagedObject,
+        paged_uri,
+        query::{Query, QueryTarget},
+        subscription::ChangeStatus,
+        AciError, Executor, ACI, SESSION_MARGIN,
+    };
+    #[derive(Default)]
+    pub struct MockClient {
+        logins: AtomicUsize,
+        refreshes: AtomicUsize,
+        token_expired: AtomicBool,
+        pages: AtomicUsize,
+        subscription_refreshes: AtomicUsize,
+        cookies: Mutex<Vec<String>>,
+        logouts: Arc<AtomicUsize>,
+        dead_servers: Mutex<Vec<String>>,
+        unavailable_servers: Mutex<Vec<String>>,
+    }
 
     impl Executor for MockClient {
         async fn execute_request(
             &self,
             request: reqwest::Request,
         ) -> anyhow::Result<reqwest::Response> {
+            if let Some(cookie) = request.headers().get(reqwest::header::COOKIE) {
+                self.cookies
+                    .lock()
+                    .unwrap()
+                    .push(cookie.to_str()?.to_string());
+            }
+            let host = request.url().host_str().unwrap_or_default().to_string();
+            if self.dead_servers.lock().unwrap().contains(&host) {
+                // Nothing listens on port 1, this gives a real connect error
+                return reqwest::Client::new()
+                    .get("http://127.0.0.1:1/")
+                    .send()
+                    .await
+                    .map_err(anyhow::Error::from);
+            }
+            if self.unavailable_servers.lock().unwrap().contains(&host) {
+                return http::response::Builder::new()
+                    .status(503)
+                    .body(String::from("Service Unavailable"))
+                    .map(reqwest::Response::from)
+                    .map_err(anyhow::Error::from);
+            }
+            if request.url().path() == "/api/aaaLogin.json" {
+                self.logins.fetch_add(1, Ordering::SeqCst);
+                self.token_expired.store(false, Ordering::SeqCst);
+                return login_request();
+            }
+            if self.token_expired.load(Ordering::SeqCst) {
+                return error_request(403, "tokenInvalid");
+            }
+
+            if request.method() == reqwest::Method::DELETE {
+                return match request.url().path() {
+                    "/api/mo/uni/tn-TEST/ap-TEST/epg-TEST.json" => empty_request(),
+                    "/api/mo/uni/tn-missing/BD-missing.json" => error_request(400, "notFound"),
+                    "/api/mo/uni/infra/vlanns-[pool]-static.json" => error_request(400, "inUse"),
+                    _ => Err(anyhow!("not supported in MockClient!")),
+                };
+            }
+
             match request.url().path() {
-                "/api/aaaLogin.json" => login_request(),
+                "/api/aaaRefresh.json" => {
+                    self.refreshes.fetch_add(1, Ordering::SeqCst);
+                    refresh_request(request)
+                }
+                "/api/class/fvTenant.json" if request.url().query() == Some("subscription=yes") => {
+                    subscription_request()
+                }
                 "/api/class/fvTenant.json" => bd_request(),
-                "/api/mo.json" => mo_request(request),
+                "/api/class/fvExpired.json" => error_request(403, "tokenInvalid"),
+                "/api/class/fvDenied.json" => error_request(403, "permissionDenied"),
+                "/api/class/fvEmpty.json" => empty_request(),
+                "/api/aaaLogout.json" => {
+                    self.logouts.fetch_add(1, Ordering::SeqCst);
+                    empty_request()
+                }
+                "/api/subscriptionRefresh.json" => {
+                    self.subscription_refreshes.fetch_add(1, Ordering::SeqCst);
+                    empty_request()
+                }
+                "/api/class/fvCEp.json"
+                    if matches!(
+                        request.url().query(),
+                        Some("rsp-subtree-include=count" | "rsp-subtree-include=faults,count")
+                    ) =>
+                {
+                    count_request()
+                }
+                "/api/class/fvCEp.json" => {
+                    self.pages.fetch_add(1, Ordering::SeqCst);
+                    paged_request(request, |total| Some(total.to_string().into()))
+                }
+                "/api/class/fvIp.json" => {
+                    self.pages.fetch_add(1, Ordering::SeqCst);
+                    paged_request(request, |total| Some(total.into()))
+                }
+                "/api/class/fvMac.json" => {
+                    self.pages.fetch_add(1, Ordering::SeqCst);
+                    paged_request(request, |_| None)
+                }
+                "/api/mo.json"
+                | "/api/mo/uni/tn-TEST.json"
+                | "/api/mo/uni/tn-TEST/ap-TEST/epg-TEST.json" => mo_request(request),
+                "/api/mo/uni/tn-missing/BD-missing.json" => error_request(400, "notFound"),
+                "/api/mo/uni/tn-invalid.json" => error_request(400, "validationFailed"),
+                "/api/class/fvInternalError.json" => http::response::Builder::new()
+                    .status(500)
+                    .body(String::from("Internal Server Error"))
+                    .map(reqwest::Response::from)
+                    .map_err(anyhow::Error::from),
                 _ => Err(anyhow!("not supported in MockClient!")),
             }
         }
@@ -226,6 +1199,100 @@ mod tests {
         Ok(response)
     }
 
+    fn refresh_request(request: reqwest::Request) -> anyhow::Result<reqwest::Response> {
+        let cookie = request.headers()[reqwest::header::COOKIE].to_str()?;
+        assert_eq!(cookie, "APIC-cookie=TOKEN");
+
+        let data = fs::read_to_string("tests/json/aaaRefresh.json")?;
+        let response = http::response::Builder::new()
+            .status(200)
+            .body(data)
+            .unwrap();
+        let response = reqwest::Response::from(response);
+
+        Ok(response)
+    }
+
+    fn error_request(status: u16, name: &str) -> anyhow::Result<reqwest::Response> {
+        let data = fs::read_to_string(format!("tests/json/error/{name}.json"))?;
+        let response = http::response::Builder::new()
+            .status(status)
+            .body(data)
+            .unwrap();
+        let response = reqwest::Response::from(response);
+
+        Ok(response)
+    }
+
+    fn subscription_request() -> anyhow::Result<reqwest::Response> {
+        let data = fs::read_to_string("tests/json/fvTenant.json")?;
+        let mut data: Value = serde_json::from_str(&data)?;
+        data["subscriptionId"] = Value::from("72057594037927937");
+        let response = http::response::Builder::new()
+            .status(200)
+            .body(data.to_string())
+            .unwrap();
+        let response = reqwest::Response::from(response);
+
+        Ok(response)
+    }
+
+    fn empty_request() -> anyhow::Result<reqwest::Response> {
+        let data = fs::read_to_string("tests/json/mo.json")?;
+        let response = http::response::Builder::new()
+            .status(200)
+            .body(data)
+            .unwrap();
+        let response = reqwest::Response::from(response);
+
+        Ok(response)
+    }
+
+    fn count_request() -> anyhow::Result<reqwest::Response> {
+        let data = fs::read_to_string("tests/json/moCount.json")?;
+        let response = http::response::Builder::new()
+            .status(200)
+            .header("content-type", "application/json")
+            .body(data)
+            .unwrap();
+        let response = reqwest::Response::from(response);
+
+        Ok(response)
+    }
+
+    /// Serves 5 `fvCEp` objects, honoring the `page` and `page-size` options.
+    /// `total_count` gives the `totalCount` of the response, if any.
+    fn paged_request(
+        request: reqwest::Request,
+        total_count: fn(u64) -> Option<Value>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let total = 5;
+        let mut page = 0;
+        let mut page_size = total;
+        for (key, value) in request.url().query_pairs() {
+            match key.as_ref() {
+                "page" => page = value.parse()?,
+                "page-size" => page_size = value.parse()?,
+                _ => {}
+            }
+        }
+
+        let imdata = (page * page_size..total.min((page + 1) * page_size))
+            .map(|index| serde_json::json!({"fvCEp": {"attributes": {"name": format!("ep-{index}")}}}))
+            .collect::<Vec<_>>();
+        let mut data = serde_json::json!({"imdata": imdata});
+        if let Some(count) = total_count(total) {
+            data["totalCount"] = count;
+        }
+        let response = http::response::Builder::new()
+            .status(200)
+            .body(data.to_string())
+            .unwrap();
+        let response = reqwest::Response::from(response);
+
+        Ok(response)
+    }
+
     fn bd_request() -> anyhow::Result<reqwest::Response> {
         let data = fs::read_to_string("tests/json/fvTenant.json")?;
         let response = http::response::Builder::new()
@@ -251,7 +1318,7 @@ mod tests {
             return Err(anyhow::anyhow!("More than one main key!"));
         }
 
-        let class = request_data.keys().nth(0).unwrap().as_str();
+        let class = request_data.keys().next().unwrap().as_str();
         match class {
             "fvAEPg" => {
                 let expected_data = fs::read_to_string("tests/json/post/epg-TEST.json")?;
@@ -264,7 +1331,20 @@ mod tests {
                     .unwrap();
                 let response = reqwest::Response::from(response);
 
-                return Ok(response);
+                Ok(response)
+            }
+            "fvTenant" => {
+                let expected_data = fs::read_to_string("tests/json/post/tn-TEST-delete.json")?;
+                let expected_json_data: Value = serde_json::from_str(&expected_data).unwrap();
+                assert_eq!(json_data, expected_json_data);
+
+                let response = http::response::Builder::new()
+                    .status(200)
+                    .body(response_data)
+                    .unwrap();
+                let response = reqwest::Response::from(response);
+
+                Ok(response)
             }
             "configExportP" => {
                 let expected_data = fs::read_to_string("tests/json/post/configExportP.json")?;
@@ -277,16 +1357,14 @@ mod tests {
                     .unwrap();
                 let response = reqwest::Response::from(response);
 
-                return Ok(response);
-            }
-            _ => {
-                return Err(anyhow::anyhow!("Class not supported by mock client"));
+                Ok(response)
             }
+            _ => Err(anyhow::anyhow!("Class not supported by mock client")),
         }
     }
 
     async fn login() -> ACI<MockClient> {
-        let executor = MockClient;
+        let executor = MockClient::default();
         let server = String::from("SERVER");
         let username = String::from("USERNAME");
         let password = String::from("PASSWORD");
@@ -302,8 +1380,515 @@ mod tests {
     async fn aci_login() {
         let aci = login().await;
 
-        assert_eq!("TOKEN", aci.token);
+        assert_eq!("TOKEN", aci.session.read().unwrap().token);
+        assert_eq!("TOKEN", aci.get_token());
+        assert_eq!(
+            Duration::from_secs(600),
+            aci.session.read().unwrap().refresh_timeout
+        );
+        assert_eq!(
+            Duration::from_secs(86400),
+            aci.session.read().unwrap().maximum_lifetime
+        );
+    }
+
+    async fn login_with_config(config: ClientConfig) -> Result<ACI<MockClient>, AciError> {
+        let servers = vec![String::from("SERVER")];
+        let username = String::from("USERNAME");
+        let password = String::from("PASSWORD");
+        ACI::new_cluster_with_executor_and_config(
+            MockClient::default(),
+            servers,
+            username,
+            password,
+            config,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn aci_login_site_fingerprint() {
+        let config = ClientConfig::new().site_fingerprint("FINGER_PRINT");
+        let aci = login_with_config(config).await.unwrap();
+
+        assert_eq!("TOKEN", aci.get_token());
+    }
+
+    #[tokio::test]
+    async fn aci_login_site_fingerprint_mismatch() {
+        let config = ClientConfig::new().site_fingerprint("OTHER_FINGER_PRINT");
+        let result = login_with_config(config).await;
+
+        match result {
+            Err(AciError::FingerprintMismatch { expected, actual }) => {
+                assert_eq!("OTHER_FINGER_PRINT", expected);
+                assert_eq!("FINGER_PRINT", actual);
+            }
+            Err(e) => panic!("unexpected error: {e}"),
+            Ok(_) => panic!("login with another fingerprint succeeded"),
+        }
+    }
+
+    #[tokio::test]
+    async fn aci_builder() {
+        let aci = ACI::builder()
+            .server("SERVER")
+            .port(8443)
+            .username("USERNAME")
+            .password("PASSWORD")
+            .domain("radius")
+            .site_fingerprint("FINGER_PRINT")
+            .connect_with_executor(MockClient::default())
+            .await
+            .unwrap();
+
+        assert_eq!("SERVER:8443", aci.active_server());
+        assert_eq!("TOKEN", aci.get_token());
+    }
+
+    #[tokio::test]
+    async fn aci_builder_invalid() {
+        let result = ACI::builder()
+            .server("https://SERVER")
+            .username("USERNAME")
+            .password("PASSWORD")
+            .connect_with_executor(MockClient::default())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(AciError::Config(ConfigError::InvalidServer(_)))
+        ));
+    }
+
+    fn cluster() -> Vec<String> {
+        vec![
+            String::from("APIC1"),
+            String::from("APIC2"),
+            String::from("APIC3"),
+        ]
+    }
+
+    async fn login_cluster(executor: MockClient) -> Result<ACI<MockClient>, AciError> {
+        let username = String::from("USERNAME");
+        let password = String::from("PASSWORD");
+        ACI::new_cluster_with_executor(executor, cluster(), username, password).await
+    }
+
+    #[tokio::test]
+    async fn cluster_login_skips_dead_server() {
+        let executor = MockClient::default();
+        executor
+            .dead_servers
+            .lock()
+            .unwrap()
+            .push(String::from("apic1"));
+        let aci = login_cluster(executor).await.unwrap();
+
+        assert_eq!("APIC2", aci.active_server());
+        assert_eq!(1, aci.executor.logins.load(Ordering::SeqCst));
+        assert_eq!("TOKEN", aci.get_token());
+    }
+
+    #[tokio::test]
+    async fn cluster_fail_over_dead_server() {
+        let aci = login_cluster(MockClient::default()).await.unwrap();
+        assert_eq!("APIC1", aci.active_server());
+
+        aci.executor
+            .dead_servers
+            .lock()
+            .unwrap()
+            .push(String::from("apic1"));
+        let tenants = aci.get_json(String::from("class/fvTenant.json")).await;
+
+        assert!(tenants.is_ok());
+        assert_eq!("APIC2", aci.active_server());
+        assert_eq!(2, aci.executor.logins.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn cluster_fail_over_server_error() {
+        let aci = login_cluster(MockClient::default()).await.unwrap();
+        aci.executor
+            .unavailable_servers
+            .lock()
+            .unwrap()
+            .extend([String::from("apic1"), String::from("apic2")]);
+
+        let tenants = aci.get_json(String::from("class/fvTenant.json")).await;
+
+        assert!(tenants.is_ok());
+        assert_eq!("APIC3", aci.active_server());
+    }
+
+    #[tokio::test]
+    async fn cluster_without_servers() {
+        let result = ACI::new_cluster_with_executor(
+            MockClient::default(),
+            Vec::new(),
+            String::from("USERNAME"),
+            String::from("PASSWORD"),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(AciError::Config(ConfigError::MissingServer))
+        ));
+    }
+
+    #[tokio::test]
+    async fn cluster_all_servers_dead() {
+        let executor = MockClient::default();
+        executor
+            .dead_servers
+            .lock()
+            .unwrap()
+            .extend(["apic1", "apic2", "apic3"].map(String::from));
+
+        let result = login_cluster(executor).await;
+
+        assert!(matches!(result, Err(AciError::Request(_))));
+    }
+
+    #[tokio::test]
+    async fn cluster_client_error_no_fail_over() {
+        let aci = login_cluster(MockClient::default()).await.unwrap();
+
+        let result = aci
+            .post_json(
+                String::from("mo/uni/tn-missing/BD-missing.json"),
+                String::from("{}"),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AciError::NotFound { .. })));
+        assert_eq!("APIC1", aci.active_server());
+    }
+
+    #[tokio::test]
+    async fn cluster_executor_error_no_fail_over() {
+        let aci = login_cluster(MockClient::default()).await.unwrap();
+
+        let result = aci.get_json(String::from("class/fvUnsupported.json")).await;
+
+        assert!(matches!(result, Err(AciError::Request(_))));
+        assert_eq!("APIC1", aci.active_server());
+        assert_eq!(1, aci.executor.logins.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn aci_logout() {
+        let aci = login().await;
+
+        aci.logout().await.unwrap();
+
+        assert_eq!("", aci.get_token());
+        assert_eq!(1, aci.executor.logouts.load(Ordering::SeqCst));
+
+        // The next request logs in again
+        aci.get_json(String::from("class/fvTenant.json"))
+            .await
+            .unwrap();
+        assert_eq!("TOKEN", aci.get_token());
+        assert_eq!(2, aci.executor.logins.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn guard_logout_on_drop() {
+        let aci = login().await;
+        let logouts = aci.executor.logouts.clone();
+
+        let guard = aci.into_guard();
+        assert_eq!("TOKEN", guard.get_token());
+        drop(guard);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(1, logouts.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn guard_logout() {
+        let aci = login().await;
+        let logouts = aci.executor.logouts.clone();
+
+        aci.into_guard().logout().await.unwrap();
+
+        assert_eq!(1, logouts.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn guard_drop_without_runtime() {
+        let aci = login().await;
+        let logouts = aci.executor.logouts.clone();
+        let guard = aci.into_guard();
+
+        std::thread::spawn(move || drop(guard)).join().unwrap();
+
+        assert_eq!(0, logouts.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn aci_refresh_token() {
+        let aci = login().await;
+
+        aci.refresh_token().await.unwrap();
+
+        assert_eq!("REFRESHED_TOKEN", aci.get_token());
+        assert_eq!(1, aci.executor.refreshes.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn session_valid_no_refresh() {
+        let aci = login().await;
+
+        aci.get_json(String::from("class/fvTenant.json"))
+            .await
+            .unwrap();
+
+        assert_eq!("TOKEN", aci.get_token());
+        assert_eq!(1, aci.executor.logins.load(Ordering::SeqCst));
+        assert_eq!(0, aci.executor.refreshes.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn session_refreshed_before_timeout() {
+        let aci = login().await;
+        aci.session.write().unwrap().refresh_timeout = SESSION_MARGIN;
+
+        aci.get_json(String::from("class/fvTenant.json"))
+            .await
+            .unwrap();
+
+        assert_eq!("REFRESHED_TOKEN", aci.get_token());
+        assert_eq!(1, aci.executor.logins.load(Ordering::SeqCst));
+        assert_eq!(1, aci.executor.refreshes.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn session_login_after_refresh_timeout() {
+        let aci = login().await;
+        aci.session.write().unwrap().refresh_timeout = Duration::ZERO;
+        aci.executor.token_expired.store(true, Ordering::SeqCst);
+
+        let first = aci.get_json(String::from("class/fvTenant.json")).await;
+        aci.session.write().unwrap().refresh_timeout = Duration::ZERO;
+        let second = aci.get_json(String::from("class/fvTenant.json")).await;
+
+        assert_eq!(2, first.unwrap().len());
+        assert_eq!(2, second.unwrap().len());
+        assert_eq!(3, aci.executor.logins.load(Ordering::SeqCst));
+        assert_eq!(0, aci.executor.refreshes.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn session_refresh_token_invalid() {
+        let aci = login().await;
+        aci.session.write().unwrap().refresh_timeout = SESSION_MARGIN;
+        aci.executor.token_expired.store(true, Ordering::SeqCst);
+
+        let tenants = aci.get_json(String::from("class/fvTenant.json")).await;
+
+        assert_eq!(2, tenants.unwrap().len());
+        assert_eq!("TOKEN", aci.get_token());
+        assert_eq!(2, aci.executor.logins.load(Ordering::SeqCst));
+        assert_eq!(0, aci.executor.refreshes.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn session_login_after_maximum_lifetime() {
+        let aci = login().await;
+        aci.session.write().unwrap().maximum_lifetime = Duration::ZERO;
+
+        aci.get_json(String::from("class/fvTenant.json"))
+            .await
+            .unwrap();
+
         assert_eq!("TOKEN", aci.get_token());
+        assert_eq!(2, aci.executor.logins.load(Ordering::SeqCst));
+        assert_eq!(0, aci.executor.refreshes.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn get_json_token_expired() {
+        let aci = login().await;
+        aci.executor.token_expired.store(true, Ordering::SeqCst);
+
+        let tenants = aci
+            .get_json(String::from("class/fvTenant.json"))
+            .await
+            .unwrap();
+
+        assert_eq!(2, tenants.len());
+        assert_eq!(2, aci.executor.logins.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn post_json_token_expired() {
+        let aci = login().await;
+        aci.executor.token_expired.store(true, Ordering::SeqCst);
+        let data = fs::read_to_string("tests/json/post/epg-TEST.json").unwrap();
+
+        aci.post_json(String::from("mo.json"), data).await.unwrap();
+
+        assert_eq!(2, aci.executor.logins.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn get_json_token_expired_retried_once() {
+        let aci = login().await;
+
+        let result = aci
+            .get::<Tenant>(String::from("class/fvExpired.json"))
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(2, aci.executor.logins.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn get_json_permission_denied_not_retried() {
+        let aci = login().await;
+
+        let result = aci.get_json(String::from("class/fvDenied.json")).await;
+
+        assert!(matches!(
+            result,
+            Err(AciError::PermissionDenied { code: 403, .. })
+        ));
+        assert_eq!(1, aci.executor.logins.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn post_json_not_found() {
+        let aci = login().await;
+        let data = r#"{"fvBD": {"attributes": {"descr": "missing"}}}"#;
+
+        let result = aci
+            .post_json(
+                String::from("mo/uni/tn-missing/BD-missing.json"),
+                String::from(data),
+            )
+            .await;
+
+        match result {
+            Err(AciError::NotFound { status, code, text }) => {
+                assert_eq!(StatusCode::BAD_REQUEST, status);
+                assert_eq!(102, code);
+                assert!(text.contains("uni/tn-missing/BD-missing"));
+            }
+            _ => panic!("expected not found, got {:?}", result),
+        }
+    }
+
+    #[tokio::test]
+    async fn post_json_validation_failed() {
+        let aci = login().await;
+        let data = r#"{"fvTenant": {"attributes": {"name": "in valid"}}}"#;
+
+        let result = aci
+            .post_json(String::from("mo/uni/tn-invalid.json"), String::from(data))
+            .await;
+
+        match result {
+            Err(AciError::ValidationFailed { code, .. }) => assert_eq!(801, code),
+            _ => panic!("expected validation failed, got {:?}", result),
+        }
+    }
+
+    #[tokio::test]
+    async fn get_json_http_error() {
+        let aci = login().await;
+
+        let result = aci
+            .get_json(String::from("class/fvInternalError.json"))
+            .await;
+
+        match result {
+            Err(AciError::Apic { status, code, text }) => {
+                assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, status);
+                assert_eq!(0, code);
+                assert_eq!("Internal Server Error", text);
+            }
+            _ => panic!("expected APIC error, got {:?}", result),
+        }
+    }
+
+    #[tokio::test]
+    async fn aci_certificate() {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let pem = String::from_utf8(key.private_key_to_pem_pkcs8().unwrap()).unwrap();
+        let aci = ACI::new_with_executor_and_certificate(
+            MockClient::default(),
+            String::from("SERVER"),
+            String::from("USERNAME"),
+            String::from("CERTIFICATE"),
+            pem,
+        )
+        .unwrap();
+
+        let tenants = aci
+            .get_json(String::from("class/fvTenant.json"))
+            .await
+            .unwrap();
+
+        assert_eq!(2, tenants.len());
+        assert_eq!(0, aci.executor.logins.load(Ordering::SeqCst));
+
+        let cookies = aci.executor.cookies.lock().unwrap();
+        let cookie = cookies[0]
+            .split("; ")
+            .map(|cookie| cookie.split_once('=').unwrap())
+            .collect::<HashMap<_, _>>();
+        assert_eq!(
+            "uni/userext/user-USERNAME/usercert-CERTIFICATE",
+            cookie["APIC-Certificate-DN"]
+        );
+        assert_eq!("v1.0", cookie["APIC-Certificate-Algorithm"]);
+        assert_eq!("fingerprint", cookie["APIC-Certificate-Fingerprint"]);
+
+        let signature = openssl::base64::decode_block(cookie["APIC-Request-Signature"]).unwrap();
+        let mut verifier = Verifier::new(MessageDigest::sha256(), &key).unwrap();
+        verifier.update(b"GET/api/class/fvTenant.json").unwrap();
+        assert!(verifier.verify(&signature).unwrap());
+    }
+
+    #[tokio::test]
+    async fn aci_builder_certificate() {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let path = std::env::temp_dir().join("rustyaci-builder-certificate.pem");
+        std::fs::write(&path, key.private_key_to_pem_pkcs8().unwrap()).unwrap();
+
+        let aci = ACI::builder()
+            .servers(cluster())
+            .username("USERNAME")
+            .signature("CERTIFICATE", &path)
+            .connect_with_executor(MockClient::default())
+            .await
+            .unwrap();
+        let tenants = aci.get_json(String::from("class/fvTenant.json")).await;
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(2, tenants.unwrap().len());
+        assert_eq!("APIC1", aci.active_server());
+        assert_eq!(0, aci.executor.logins.load(Ordering::SeqCst));
+        let cookies = aci.executor.cookies.lock().unwrap();
+        assert!(cookies[0]
+            .contains("APIC-Certificate-DN=uni/userext/user-USERNAME/usercert-CERTIFICATE"));
+    }
+
+    #[tokio::test]
+    async fn aci_certificate_invalid_key() {
+        let result = ACI::new_with_executor_and_certificate(
+            MockClient::default(),
+            String::from("SERVER"),
+            String::from("USERNAME"),
+            String::from("CERTIFICATE"),
+            String::from("not a key"),
+        );
+
+        assert!(result.is_err());
     }
 
     #[tokio::test]
@@ -333,6 +1918,369 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn aci_get_json_query() {
+        let aci = login().await;
+        let query = Query::class("fvTenant")
+            .query_target(QueryTarget::SelfOnly)
+            .build()
+            .unwrap();
+
+        let tenants = aci.get_json(query).await.unwrap();
+
+        assert_eq!(2, tenants.len());
+    }
+
+    #[tokio::test]
+    async fn aci_get_paged() {
+        let aci = login().await;
+
+        let endpoints = aci
+            .get_paged::<Value>(String::from("class/fvCEp.json"), 2)
+            .map_ok(|ep| {
+                ep["fvCEp"]["attributes"]["name"]
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(endpoints, vec!["ep-0", "ep-1", "ep-2", "ep-3", "ep-4"]);
+        assert_eq!(3, aci.executor.pages.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn aci_get_paged_numeric_count() {
+        let aci = login().await;
+
+        let endpoints = aci
+            .get_all_paged::<Value>(String::from("class/fvIp.json"), 2)
+            .await
+            .unwrap();
+
+        assert_eq!(5, endpoints.len());
+        assert_eq!(3, aci.executor.pages.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn aci_get_paged_without_count() {
+        let aci = login().await;
+
+        let endpoints = aci
+            .get_all_paged::<Value>(String::from("class/fvMac.json"), 2)
+            .await
+            .unwrap();
+        assert_eq!(5, endpoints.len());
+        assert_eq!(3, aci.executor.pages.load(Ordering::SeqCst));
+
+        // A last page that is full is followed by an empty one
+        let endpoints = aci
+            .get_all_paged::<Value>(String::from("class/fvMac.json"), 5)
+            .await
+            .unwrap();
+        assert_eq!(5, endpoints.len());
+        assert_eq!(5, aci.executor.pages.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn aci_get_all_paged() {
+        let aci = login().await;
+
+        let endpoints = aci
+            .get_all_paged::<Value>(String::from("class/fvCEp.json?order-by=fvCEp.name"), 5)
+            .await
+            .unwrap();
+
+        assert_eq!(5, endpoints.len());
+        assert_eq!(1, aci.executor.pages.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn aci_get_paged_empty() {
+        let aci = login().await;
+
+        let data = aci
+            .get_all_paged::<Value>(String::from("class/fvEmpty.json"), 10)
+            .await
+            .unwrap();
+
+        assert!(data.is_empty());
+    }
+
+    #[tokio::test]
+    async fn aci_get_with_metadata() {
+        let aci = login().await;
+
+        let response = aci
+            .get_with_metadata::<Tenant>(String::from("class/fvTenant.json"))
+            .await
+            .unwrap();
+
+        assert_eq!(2, response.items.len());
+        assert_eq!(2, response.total_count);
+        assert_eq!(StatusCode::OK, response.status);
+
+        let response = aci
+            .get_json_with_metadata(String::from("class/fvCEp.json?page=1&page-size=2"))
+            .await
+            .unwrap();
+
+        assert_eq!(2, response.items.len());
+        assert_eq!(5, response.total_count);
+    }
+
+    #[tokio::test]
+    async fn aci_count() {
+        let aci = login().await;
+
+        let count = aci.count(String::from("class/fvCEp.json")).await.unwrap();
+        assert_eq!(5, count);
+        let count = aci
+            .count(String::from("class/fvCEp.json?rsp-subtree-include=faults"))
+            .await
+            .unwrap();
+        assert_eq!(5, count);
+
+        let response = aci
+            .get_json_with_metadata(String::from("class/fvCEp.json?rsp-subtree-include=count"))
+            .await
+            .unwrap();
+        assert_eq!(
+            "application/json",
+            response.headers["content-type"].to_str().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    #[allow(clippy::result_large_err)]
+    async fn aci_subscribe() {
+        let aci = login().await;
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        // Stand-in for the event channel of the APIC
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut path = String::new();
+            let mut socket = tokio_tungstenite::accept_hdr_async(
+                stream,
+                |request: &http::Request<()>, response| {
+                    path = request.uri().path().to_string();
+                    Ok(response)
+                },
+            )
+            .await
+            .unwrap();
+
+            let events = fs::read_to_string("tests/json/subscription/events.json").unwrap();
+            socket.send(Message::Text(events)).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            socket.close(None).await.unwrap();
+            path
+        });
+
+        let events = aci
+            .subscribe_to(
+                0,
+                &format!("ws://{address}/socket{}", aci.get_token()),
+                String::from("class/fvTenant.json"),
+                Duration::from_millis(50),
+            )
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!("/socketTOKEN", server.await.unwrap());
+        let changes = events
+            .iter()
+            .map(|event| (event.dn().unwrap(), event.status))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec![
+                ("uni/tn-test", ChangeStatus::Created),
+                ("uni/tn-common", ChangeStatus::Modified),
+                ("uni/tn-test", ChangeStatus::Deleted),
+            ]
+        );
+        assert_eq!(vec!["72057594037927937"], events[0].subscription_ids);
+        assert!(aci.executor.subscription_refreshes.load(Ordering::SeqCst) >= 1);
+    }
+
+    #[tokio::test]
+    async fn aci_subscribe_certificate() {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let pem = String::from_utf8(key.private_key_to_pem_pkcs8().unwrap()).unwrap();
+        let aci = ACI::new_with_executor_and_certificate(
+            MockClient::default(),
+            String::from("SERVER"),
+            String::from("USERNAME"),
+            String::from("CERTIFICATE"),
+            pem,
+        )
+        .unwrap();
+
+        let result = aci.subscribe(String::from("class/fvTenant.json")).await;
+
+        assert!(matches!(result, Err(AciError::NoSessionToken)));
+    }
+
+    #[tokio::test]
+    async fn aci_subscribe_without_subscription_id() {
+        let aci = login().await;
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let _socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        });
+
+        let result = aci
+            .subscribe_to(
+                0,
+                &format!("ws://{address}/socket{}", aci.get_token()),
+                String::from("class/fvEmpty.json"),
+                Duration::from_secs(60),
+            )
+            .await;
+
+        match result {
+            Err(AciError::UnexpectedResponse(text)) => assert!(text.contains("subscriptionId")),
+            Err(e) => panic!("expected an unexpected response error, got {e}"),
+            Ok(_) => panic!("expected an error"),
+        }
+    }
+
+    async fn login_pair() -> ACI<MockClient> {
+        let servers = vec![String::from("APIC1"), String::from("APIC2")];
+        let username = String::from("USERNAME");
+        let password = String::from("PASSWORD");
+        ACI::new_cluster_with_executor(MockClient::default(), servers, username, password)
+            .await
+            .unwrap()
+    }
+
+    /// Stand-in for an event channel that stays open without events.
+    async fn silent_socket() -> std::net::SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let _socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        });
+        address
+    }
+
+    #[tokio::test]
+    async fn aci_subscribe_no_fail_over() {
+        let aci = login_pair().await;
+        let address = silent_socket().await;
+        aci.executor
+            .unavailable_servers
+            .lock()
+            .unwrap()
+            .push(String::from("apic1"));
+
+        let result = aci
+            .subscribe_to(
+                0,
+                &format!("ws://{address}/socket{}", aci.get_token()),
+                String::from("class/fvTenant.json"),
+                Duration::from_secs(60),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AciError::Apic { .. })));
+        assert_eq!("APIC1", aci.active_server());
+    }
+
+    #[tokio::test]
+    async fn aci_subscribe_ends_on_fail_over() {
+        let aci = login_pair().await;
+        let address = silent_socket().await;
+        let events = aci
+            .subscribe_to(
+                0,
+                &format!("ws://{address}/socket{}", aci.get_token()),
+                String::from("class/fvTenant.json"),
+                Duration::from_millis(50),
+            )
+            .await
+            .unwrap();
+        let mut events = std::pin::pin!(events);
+
+        aci.executor
+            .unavailable_servers
+            .lock()
+            .unwrap()
+            .push(String::from("apic1"));
+        aci.get_json(String::from("class/fvTenant.json"))
+            .await
+            .unwrap();
+        assert_eq!("APIC2", aci.active_server());
+
+        match events.next().await {
+            Some(Err(AciError::ServerChanged { subscribed, active })) => {
+                assert_eq!("APIC1", subscribed);
+                assert_eq!("APIC2", active);
+            }
+            _ => panic!("expected the subscription to end with ServerChanged"),
+        }
+        assert!(events.next().await.is_none());
+        assert_eq!(
+            0,
+            aci.executor.subscription_refreshes.load(Ordering::SeqCst)
+        );
+    }
+
+    #[tokio::test]
+    async fn aci_count_without_mo_count() {
+        let aci = login().await;
+
+        assert!(matches!(
+            aci.count(String::from("class/fvEmpty.json")).await,
+            Err(AciError::UnexpectedResponse(_))
+        ));
+    }
+
+    #[test]
+    fn count_uri_merges_include() {
+        assert_eq!(
+            "class/fvCEp.json?rsp-subtree-include=count",
+            count_uri("class/fvCEp.json")
+        );
+        assert_eq!(
+            "class/fvCEp.json?order-by=fvCEp.mac&rsp-subtree-include=count",
+            count_uri("class/fvCEp.json?order-by=fvCEp.mac")
+        );
+        assert_eq!(
+            "class/fvCEp.json?rsp-subtree-include=faults,count&order-by=fvCEp.mac",
+            count_uri("class/fvCEp.json?rsp-subtree-include=faults&order-by=fvCEp.mac")
+        );
+        assert_eq!(
+            "class/fvCEp.json?rsp-subtree-include=count,no-scoped",
+            count_uri("class/fvCEp.json?rsp-subtree-include=count,no-scoped")
+        );
+    }
+
+    #[test]
+    fn paged_uri_options() {
+        assert_eq!(
+            "class/fvCEp.json?page=0&page-size=10",
+            paged_uri("class/fvCEp.json", 0, 10)
+        );
+        assert_eq!(
+            "class/fvCEp.json?order-by=fvCEp.mac&page=2&page-size=10",
+            paged_uri("class/fvCEp.json?order-by=fvCEp.mac", 2, 10)
+        );
+    }
+
     #[derive(Deserialize, Debug)]
     pub struct Tenant {
         #[serde(rename = "fvTenant")]
@@ -366,6 +2314,21 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn aci_get_tenants() {
+        let aci = login().await;
+
+        let tenants = aci.get_tenants().await.unwrap();
+        assert_eq!(
+            vec!["infra", "common"],
+            tenants
+                .iter()
+                .map(|tenant| tenant.name.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Some("uni/tn-common".parse().unwrap()), tenants[1].dn);
+    }
+
     pub struct FlatTenant {
         pub name: String,
     }
@@ -416,6 +2379,112 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn aci_post_mo() {
+        let aci = login().await;
+        let epg = ManagedObject::new("fvAEPg")
+            .dn("uni/tn-TEST/ap-TEST/epg-TEST")
+            .attribute("name", "TEST");
+
+        match aci.post_mo(&epg).await {
+            Ok(()) => return,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn aci_post_mo_validated() {
+        let meta = crate::meta::AciMeta::from_file("tests/json/meta/aci-meta.json").unwrap();
+        let aci = login().await.with_meta(meta);
+        let epg = ManagedObject::new("fvAEPg")
+            .dn("uni/tn-TEST/ap-TEST/epg-TEST")
+            .attribute("name", "TEST");
+
+        match aci.post_mo(&epg).await {
+            Ok(()) => return,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn aci_post_json_invalid() {
+        let meta = crate::meta::AciMeta::from_file("tests/json/meta/aci-meta.json").unwrap();
+        let aci = login().await.with_meta(meta);
+        let data = r#"{"fvAEPg": {"attributes": {"dn": "uni/tn-TEST/ap-TEST/epg-TEST", "name": "TEST", "prefGrMemb": "yes"}}}"#;
+
+        match aci
+            .post_json(String::from("mo.json"), data.to_string())
+            .await
+        {
+            Err(AciError::Invalid(errors)) => {
+                assert_eq!(1, errors.len());
+                assert_eq!("$.fvAEPg.attributes.prefGrMemb", errors[0].path);
+            }
+            result => panic!("expected validation error, got {:?}", result),
+        }
+    }
+
+    #[tokio::test]
+    async fn aci_post_mo_without_dn() {
+        let aci = login().await;
+        let epg = ManagedObject::new("fvAEPg").attribute("name", "TEST");
+
+        assert!(matches!(
+            aci.post_mo(&epg).await,
+            Err(AciError::MissingDn(class)) if class == "fvAEPg"
+        ));
+    }
+
+    #[tokio::test]
+    async fn aci_delete() {
+        let aci = login().await;
+        let dn = "uni/tn-TEST/ap-TEST/epg-TEST".parse().unwrap();
+
+        match aci.delete(&dn).await {
+            Ok(()) => return,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn aci_delete_not_found() {
+        let aci = login().await;
+        let dn = "uni/tn-missing/BD-missing".parse().unwrap();
+
+        assert!(matches!(
+            aci.delete(&dn).await,
+            Err(AciError::NotFound { code: 102, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn aci_delete_in_use() {
+        let aci = login().await;
+        let dn = "uni/infra/vlanns-[pool]-static".parse().unwrap();
+
+        match aci.delete(&dn).await {
+            Err(AciError::Apic { code, text, .. }) => {
+                assert_eq!(107, code);
+                assert!(text.contains("in use"));
+            }
+            result => panic!("expected APIC error, got {:?}", result),
+        }
+    }
+
+    #[tokio::test]
+    async fn aci_delete_children() {
+        let aci = login().await;
+        let tenant = ManagedObject::new("fvTenant")
+            .dn("uni/tn-TEST")
+            .child(ManagedObject::new("fvBD").attribute("name", "BD1"))
+            .child(ManagedObject::new("fvAp").attribute("name", "AP1"));
+
+        match aci.delete_children(&tenant).await {
+            Ok(()) => return,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
     #[tokio::test]
     async fn aci_post_json_inline_data() {
         let aci = login().await;
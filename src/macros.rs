@@ -2,10 +2,64 @@
 pub enum MacroError {
     #[error("Missing Field: {0}")]
     MissingField(String),
+    #[error("Invalid Field: {0}")]
+    InvalidField(String),
+    #[error("Invalid Child {0}: {1}")]
+    InvalidChild(String, String),
+}
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{dn::Dn, query::Query};
+
+/// Objects of an ACI class, usually implemented through
+/// `#[derive(AciObject)]`, e.g.
+///
+/// ```
+/// use rustyaci::{dn::Dn, AciObject};
+///
+/// #[derive(Debug, AciObject)]
+/// #[aci(class = "fvBD")]
+/// struct Bd {
+///     dn: Dn,
+///     name: String,
+///     #[aci(rename = "nameAlias", default)]
+///     name_alias: String,
+/// }
+///
+/// let uri = Bd::class_query().build().unwrap();
+/// assert_eq!("class/fvBD.json", uri);
+/// ```
+///
+/// Fields are read from the attribute of the same name unless renamed with
+/// `#[aci(rename = "..")]`. Missing attributes are an error, except for
+/// `Option` fields and fields with `#[aci(default)]` or
+/// `#[aci(default = ..)]`. Fields marked with `#[aci(children)]` hold the
+/// children of another class, e.g. `Vec<Subnet>`.
+pub trait AciObject: private::AciClass + Serialize + DeserializeOwned {
+    /// The `dn` attribute of the object, if it has a valid one.
+    fn dn(&self) -> Option<Dn>;
+
+    /// A query for all objects of the class, see [`ACI::get`](crate::ACI::get).
+    fn class_query() -> Query {
+        Query::class(Self::CLASS_NAME)
+    }
+
+    /// A query for the object itself, which fails to build without a `dn`.
+    fn mo_query(&self) -> Query {
+        Query::mo(self.dn().map(String::from).unwrap_or_default())
+    }
 }
 
 pub mod private {
+    pub use serde;
+    use serde::{de::DeserializeOwned, Serialize};
+    pub use serde_json;
+    use serde_json::Value;
+    use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
     use super::MacroError;
+    use crate::dn::Dn;
 
     pub trait GetValue {
         fn get_value(value: serde_json::Value, field_name: &str) -> Result<Self, MacroError>
@@ -13,30 +67,227 @@ pub mod private {
             Self: Sized;
     }
 
+    /// The class of a struct generated by [`aci_struct!`](crate::aci_struct).
+    pub trait AciClass {
+        const CLASS_NAME: &'static str;
+    }
+
+    /// Child collections of a struct generated by
+    /// [`aci_struct!`](crate::aci_struct), read from the `children` array by
+    /// the class name of the child.
+    pub trait Children {
+        fn get_children(object: &Value) -> Result<Self, MacroError>
+        where
+            Self: Sized;
+
+        fn put_children(&self, children: &mut Vec<Value>) -> Result<(), MacroError>;
+    }
+
+    impl<T: AciClass + DeserializeOwned + Serialize> Children for Vec<T> {
+        fn get_children(object: &Value) -> Result<Self, MacroError> {
+            let Some(children) = object["children"].as_array() else {
+                return Ok(Vec::new());
+            };
+            children
+                .iter()
+                .filter(|child| child.get(T::CLASS_NAME).is_some())
+                .map(|child| {
+                    serde_json::from_value(child.clone()).map_err(|error| {
+                        MacroError::InvalidChild(T::CLASS_NAME.to_string(), error.to_string())
+                    })
+                })
+                .collect()
+        }
+
+        fn put_children(&self, children: &mut Vec<Value>) -> Result<(), MacroError> {
+            for child in self {
+                let child = serde_json::to_value(child).map_err(|error| {
+                    MacroError::InvalidChild(T::CLASS_NAME.to_string(), error.to_string())
+                })?;
+                children.push(child);
+            }
+            Ok(())
+        }
+    }
+
+    /// Converts a field back into the attribute posted to the APIC, `None`
+    /// skips the attribute.
+    pub trait PutValue {
+        fn put_value(&self) -> Option<Value>;
+    }
+
+    fn get_str<'a>(value: &'a Value, field_name: &str) -> Result<&'a str, MacroError> {
+        match value[field_name].as_str() {
+            Some(value) => Ok(value),
+            None => Err(MacroError::MissingField(field_name.to_string())),
+        }
+    }
+
+    fn invalid(field_name: &str) -> MacroError {
+        MacroError::InvalidField(field_name.to_string())
+    }
+
     impl GetValue for String {
         fn get_value(value: serde_json::Value, field_name: &str) -> Result<Self, MacroError> {
-            let value = value[field_name].as_str();
-            match value {
-                Some(value) => Ok(value.to_string()),
-                None => Err(MacroError::MissingField(field_name.to_string())),
+            get_str(&value, field_name).map(String::from)
+        }
+    }
+
+    impl PutValue for String {
+        fn put_value(&self) -> Option<Value> {
+            Some(Value::from(self.as_str()))
+        }
+    }
+
+    impl GetValue for Dn {
+        fn get_value(value: serde_json::Value, field_name: &str) -> Result<Self, MacroError> {
+            get_str(&value, field_name)?
+                .parse()
+                .map_err(|_| invalid(field_name))
+        }
+    }
+
+    impl PutValue for Dn {
+        fn put_value(&self) -> Option<Value> {
+            Some(Value::from(self.to_string()))
+        }
+    }
+
+    /// Optional fields are `None` if the attribute is missing.
+    impl<T: GetValue> GetValue for Option<T> {
+        fn get_value(value: serde_json::Value, field_name: &str) -> Result<Self, MacroError> {
+            if value[field_name].is_null() {
+                return Ok(None);
             }
+            T::get_value(value, field_name).map(Some)
+        }
+    }
+
+    impl<T: PutValue> PutValue for Option<T> {
+        fn put_value(&self) -> Option<Value> {
+            self.as_ref()?.put_value()
         }
     }
 
-    impl GetValue for u64 {
+    /// Only `yes`/`no`, anything else is invalid, so the value is posted back
+    /// as it was read. Flags using `enabled`/`disabled` or `true`/`false` are
+    /// read into [`EnabledDisabled`](super::EnabledDisabled) and
+    /// [`TrueFalse`](super::TrueFalse) instead, which convert from and to
+    /// `bool`.
+    impl GetValue for bool {
         fn get_value(value: serde_json::Value, field_name: &str) -> Result<Self, MacroError> {
-            let value = value[field_name].as_u64();
-            match value {
-                Some(value) => Ok(value),
-                None => Err(MacroError::MissingField(field_name.to_string())),
+            match get_str(&value, field_name)? {
+                "yes" => Ok(true),
+                "no" => Ok(false),
+                _ => Err(invalid(field_name)),
             }
         }
     }
+
+    impl PutValue for bool {
+        fn put_value(&self) -> Option<Value> {
+            Some(Value::from(if *self { "yes" } else { "no" }))
+        }
+    }
+
+    /// Numbers are accepted both as JSON numbers and as strings like `"500"`,
+    /// and written as strings like the APIC does.
+    macro_rules! number {
+        ($($type:ty),*) => {
+            $(
+                impl GetValue for $type {
+                    fn get_value(value: serde_json::Value, field_name: &str) -> Result<Self, MacroError> {
+                        match &value[field_name] {
+                            Value::Null => Err(MacroError::MissingField(field_name.to_string())),
+                            Value::String(number) => number.trim().parse().map_err(|_| invalid(field_name)),
+                            number => serde_json::from_value(number.clone()).map_err(|_| invalid(field_name)),
+                        }
+                    }
+                }
+
+                impl PutValue for $type {
+                    fn put_value(&self) -> Option<Value> {
+                        Some(Value::from(self.to_string()))
+                    }
+                }
+            )*
+        };
+    }
+
+    number!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+
+    /// Timestamps like `modTs`, e.g. `2024-03-17T15:37:25.123+00:00`.
+    impl GetValue for OffsetDateTime {
+        fn get_value(value: serde_json::Value, field_name: &str) -> Result<Self, MacroError> {
+            OffsetDateTime::parse(get_str(&value, field_name)?, &Rfc3339)
+                .map_err(|_| invalid(field_name))
+        }
+    }
+
+    impl PutValue for OffsetDateTime {
+        fn put_value(&self) -> Option<Value> {
+            self.format(&Rfc3339).ok().map(Value::from)
+        }
+    }
+
+    /// Comma-separated lists such as `rolesR`.
+    impl GetValue for Vec<String> {
+        fn get_value(value: serde_json::Value, field_name: &str) -> Result<Self, MacroError> {
+            Ok(get_str(&value, field_name)?
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(String::from)
+                .collect())
+        }
+    }
+
+    impl PutValue for Vec<String> {
+        fn put_value(&self) -> Option<Value> {
+            Some(Value::from(self.join(",")))
+        }
+    }
 }
 
+/// Generates a struct for the attributes of an ACI class, e.g.
+///
+/// ```
+/// rustyaci::aci_struct!(
+///     /// A bridge domain
+///     #[derive(Clone)]
+///     Bd,
+///     "fvBD",
+///     {
+///         name: String,
+///         #[aci(rename = "nameAlias")]
+///         name_alias: Option<String>,
+///         #[aci(rename = "unkMacUcastAct", default = String::from("proxy"))]
+///         unknown_unicast: String,
+///     }
+/// );
+/// ```
+///
+/// Fields are read from the attribute of the same name unless renamed with
+/// `#[aci(rename = "..")]`. Fields marked with `#[aci(children)]` hold the
+/// children of another generated struct, e.g.
+/// `#[aci(children)] bds: Vec<Bd>`. Missing attributes are an error, except
+/// for `Option` fields and fields with `#[aci(default)]`
+/// (`Default::default()`) or `#[aci(default = ..)]`. `Debug` is always
+/// derived.
+///
+/// A `bool` field only reads `yes`/`no`. Flags using `enabled`/`disabled` or
+/// `true`/`false` need an [`EnabledDisabled`](crate::macros::EnabledDisabled)
+/// or [`TrueFalse`](crate::macros::TrueFalse) field, so they are posted back
+/// with the same words.
 #[macro_export]
 macro_rules! aci_struct {
-    ($struct_name:ident, $root:expr, { $($field_name:ident : $field_type:ty),* $(,)? }) => {
+    (
+        $(#[$meta:meta])*
+        $struct_name:ident, $root:expr, {
+            $( $(#[aci($($option:tt)*)])* $field_name:ident : $field_type:ty ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
         #[derive(Debug)]
         pub struct $struct_name {
             $(pub $field_name: $field_type),*
@@ -48,18 +299,306 @@ macro_rules! aci_struct {
                 D: serde::Deserializer<'de>,
             {
                 let value: serde_json::Value = serde_json::Value::deserialize(deserializer)?;
+                let object = &value[$root];
 
-                use $crate::macros::private::GetValue;
                 Ok($struct_name {
                     $(
-                        $field_name: <$field_type>::get_value(value[$root]["attributes"].clone(), stringify!($field_name)).map_err(serde::de::Error::custom)?
+                        $field_name: $crate::__aci_get_value!(
+                            object,
+                            $crate::__aci_attribute!($field_name $($($option)*,)*),
+                            $field_type;
+                            $($($option)*,)*
+                        ).map_err(serde::de::Error::custom)?
                     ),*
                 })
             }
         }
+
+        impl serde::Serialize for $struct_name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                let mut attributes = serde_json::Map::new();
+                #[allow(unused_mut)]
+                let mut children: Vec<serde_json::Value> = Vec::new();
+                $(
+                    $crate::__aci_put_value!(
+                        &self.$field_name,
+                        attributes,
+                        children,
+                        $crate::__aci_attribute!($field_name $($($option)*,)*);
+                        $($($option)*,)*
+                    );
+                )*
+
+                let mut object = serde_json::json!({ "attributes": attributes });
+                if !children.is_empty() {
+                    object["children"] = serde_json::Value::Array(children);
+                }
+                serde::Serialize::serialize(&serde_json::json!({ $root: object }), serializer)
+            }
+        }
+
+        impl $crate::macros::private::AciClass for $struct_name {
+            const CLASS_NAME: &'static str = $root;
+        }
+
+        impl $struct_name {
+            /// The `mo/<dn>.json` URI and the body to post the object with
+            /// [`ACI::post_json`]($crate::ACI::post_json). The object needs a `dn`.
+            #[allow(dead_code)]
+            pub fn to_post_json(&self) -> Result<(String, String), $crate::AciError> {
+                let body = serde_json::to_value(self)?;
+                let dn = body[$root]["attributes"]["dn"].as_str().unwrap_or_default();
+                let uri = $crate::query::Query::mo(dn).build()?;
+
+                Ok((uri, body.to_string()))
+            }
+        }
+    };
+}
+
+/// The attribute name of a field of [`aci_struct!`] given its options.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __aci_attribute {
+    ($field_name:ident) => {
+        stringify!($field_name)
+    };
+    ($field_name:ident rename = $name:literal, $($rest:tt)*) => {
+        $name
+    };
+    ($field_name:ident default $(= $default:expr)?, $($rest:tt)*) => {
+        $crate::__aci_attribute!($field_name $($rest)*)
+    };
+    ($field_name:ident children, $($rest:tt)*) => {
+        $crate::__aci_attribute!($field_name $($rest)*)
+    };
+    ($field_name:ident $option:ident $($rest:tt)*) => {
+        compile_error!(concat!("unknown aci_struct! option: ", stringify!($option)))
+    };
+}
+
+/// Reads a field of [`aci_struct!`], falling back to its default if any.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __aci_get_value {
+    ($object:ident, $name:expr, $field_type:ty;) => {
+        <$field_type as $crate::macros::private::GetValue>::get_value(
+            $object["attributes"].clone(),
+            $name,
+        )
+    };
+    ($object:ident, $name:expr, $field_type:ty; children, $($rest:tt)*) => {
+        <$field_type as $crate::macros::private::Children>::get_children($object)
+    };
+    ($object:ident, $name:expr, $field_type:ty; default, $($rest:tt)*) => {
+        $crate::__aci_get_value!($object, $name, $field_type; default = Default::default(),)
+    };
+    ($object:ident, $name:expr, $field_type:ty; default = $default:expr, $($rest:tt)*) => {
+        if $object["attributes"][$name].is_null() {
+            Ok($default)
+        } else {
+            $crate::__aci_get_value!($object, $name, $field_type;)
+        }
+    };
+    ($object:ident, $name:expr, $field_type:ty; $option:ident $(= $value:expr)?, $($rest:tt)*) => {
+        $crate::__aci_get_value!($object, $name, $field_type; $($rest)*)
+    };
+}
+
+/// Writes a field of [`aci_struct!`] either as attribute or as children.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __aci_put_value {
+    ($value:expr, $attributes:ident, $children:ident, $name:expr;) => {
+        if let Some(value) = $crate::macros::private::PutValue::put_value($value) {
+            $attributes.insert($name.to_string(), value);
+        }
+    };
+    ($value:expr, $attributes:ident, $children:ident, $name:expr; children, $($rest:tt)*) => {
+        $crate::macros::private::Children::put_children($value, &mut $children)
+            .map_err(serde::ser::Error::custom)?
+    };
+    ($value:expr, $attributes:ident, $children:ident, $name:expr; $option:ident $(= $default:expr)?, $($rest:tt)*) => {
+        $crate::__aci_put_value!($value, $attributes, $children, $name; $($rest)*)
+    };
+}
+
+/// Generates an enum for the valid values of an attribute, which can be used
+/// as field type of [`aci_struct!`] and `#[derive(AciObject)]`, e.g.
+///
+/// ```
+/// rustyaci::aci_enum!(
+///     /// Policy control enforcement of a VRF
+///     PcEnfPref {
+///         Enforced = "enforced",
+///         Unenforced = "unenforced",
+///     }
+/// );
+///
+/// assert_eq!(Some(PcEnfPref::Enforced), "enforced".parse().ok());
+/// assert_eq!("unenforced", PcEnfPref::Unenforced.as_str());
+/// ```
+///
+/// Other values are an error unless the enum ends with a fallback variant,
+/// e.g. `_ => Other`, which keeps them as `Other(String)`. This way values
+/// added by newer APIC versions can still be read and posted back.
+#[macro_export]
+macro_rules! aci_enum {
+    (
+        $(#[$meta:meta])*
+        $enum_name:ident {
+            $( $(#[$variant_meta:meta])* $variant:ident = $value:literal, )*
+            _ => $other:ident $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $enum_name {
+            $( $(#[$variant_meta])* $variant, )*
+            /// A value that isn't known yet.
+            $other(String),
+        }
+
+        impl $enum_name {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $( $enum_name::$variant => $value, )*
+                    $enum_name::$other(value) => value,
+                }
+            }
+        }
+
+        impl ::std::str::FromStr for $enum_name {
+            type Err = $crate::macros::MacroError;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                match value {
+                    $( $value => Ok($enum_name::$variant), )*
+                    _ => Ok($enum_name::$other(value.to_string())),
+                }
+            }
+        }
+
+        $crate::__aci_enum_values!($enum_name);
+    };
+    (
+        $(#[$meta:meta])*
+        $enum_name:ident {
+            $( $(#[$variant_meta:meta])* $variant:ident = $value:literal ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $enum_name {
+            $( $(#[$variant_meta])* $variant ),*
+        }
+
+        impl $enum_name {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $( $enum_name::$variant => $value ),*
+                }
+            }
+        }
+
+        impl ::std::str::FromStr for $enum_name {
+            type Err = $crate::macros::MacroError;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                match value {
+                    $( $value => Ok($enum_name::$variant), )*
+                    _ => Err($crate::macros::MacroError::InvalidField(value.to_string())),
+                }
+            }
+        }
+
+        $crate::__aci_enum_values!($enum_name);
+    };
+}
+
+/// The parts of [`aci_enum!`] built on `as_str` and `FromStr`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __aci_enum_values {
+    ($enum_name:ident) => {
+        impl ::std::fmt::Display for $enum_name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl $crate::macros::private::GetValue for $enum_name {
+            fn get_value(
+                value: $crate::macros::private::serde_json::Value,
+                field_name: &str,
+            ) -> Result<Self, $crate::macros::MacroError> {
+                match value[field_name].as_str() {
+                    Some(value) => value.parse().map_err(|_| {
+                        $crate::macros::MacroError::InvalidField(field_name.to_string())
+                    }),
+                    None => Err($crate::macros::MacroError::MissingField(
+                        field_name.to_string(),
+                    )),
+                }
+            }
+        }
+
+        impl $crate::macros::private::PutValue for $enum_name {
+            fn put_value(&self) -> Option<$crate::macros::private::serde_json::Value> {
+                Some($crate::macros::private::serde_json::Value::from(
+                    self.as_str(),
+                ))
+            }
+        }
     };
 }
 
+/// An [`aci_enum!`] for flags that don't use the `yes`/`no` of `bool`,
+/// convertible from and to `bool`.
+macro_rules! flag {
+    (
+        $(#[$meta:meta])*
+        $enum_name:ident { $on:ident = $on_value:literal, $off:ident = $off_value:literal }
+    ) => {
+        aci_enum!(
+            $(#[$meta])*
+            $enum_name {
+                $on = $on_value,
+                $off = $off_value,
+            }
+        );
+
+        impl From<bool> for $enum_name {
+            fn from(value: bool) -> Self {
+                if value {
+                    $enum_name::$on
+                } else {
+                    $enum_name::$off
+                }
+            }
+        }
+
+        impl From<$enum_name> for bool {
+            fn from(value: $enum_name) -> Self {
+                value == $enum_name::$on
+            }
+        }
+    };
+}
+
+flag!(
+    /// Flags like `adminSt` of some classes, which use `enabled`/`disabled`.
+    EnabledDisabled { Enabled = "enabled", Disabled = "disabled" }
+);
+
+flag!(
+    /// Flags like `remoteUser` of `aaaLogin`, which use `true`/`false`.
+    TrueFalse { True = "true", False = "false" }
+);
+
 #[cfg(test)]
 mod tests {
     crate::aci_struct!(
@@ -86,4 +625,333 @@ mod tests {
         assert_eq!(tenant.name, "TenantName");
         assert_eq!(tenant.bytes, 500);
     }
+
+    #[test]
+    fn macro_number_round_trip_test() {
+        let json_data = r#"{"fvTenant": {"attributes": {"name": "TenantName", "bytes": "500"}}}"#;
+        let tenant: Tenant = serde_json::from_str(json_data).expect("Failed to deserialize");
+
+        let value = serde_json::to_value(&tenant).unwrap();
+        assert_eq!(
+            value["fvTenant"]["attributes"]["bytes"],
+            serde_json::json!("500")
+        );
+        assert_eq!(
+            value,
+            serde_json::from_str::<serde_json::Value>(json_data).unwrap()
+        );
+        let tenant: Tenant = serde_json::from_value(value).expect("Failed to deserialize");
+        assert_eq!(tenant.bytes, 500);
+    }
+
+    crate::aci_struct!(
+        Epg,
+        "fvAEPg",
+        {
+            dn: crate::dn::Dn,
+        }
+    );
+
+    crate::aci_struct!(
+        Bd,
+        "fvBD",
+        {
+            dn: String,
+            name: String,
+            descr: Option<String>,
+            mtu: Option<u64>,
+        }
+    );
+
+    #[test]
+    fn macro_serialize_test() {
+        let json_data =
+            r#"{"fvBD": {"attributes": {"dn": "uni/tn-A/BD-B", "name": "B", "mtu": "9000"}}}"#;
+        let mut bd: Bd = serde_json::from_str(json_data).expect("Failed to deserialize");
+        assert_eq!(bd.descr, None);
+        assert_eq!(bd.mtu, Some(9000));
+
+        assert_eq!(
+            serde_json::to_value(&bd).unwrap(),
+            serde_json::from_str::<serde_json::Value>(json_data).unwrap()
+        );
+
+        bd.descr = Some(String::from("changed"));
+        bd.mtu = None;
+        let (uri, body) = bd.to_post_json().unwrap();
+        assert_eq!(uri, "mo/uni/tn-A/BD-B.json");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&body).unwrap(),
+            serde_json::json!({"fvBD": {"attributes": {"dn": "uni/tn-A/BD-B", "name": "B", "descr": "changed"}}})
+        );
+    }
+
+    #[test]
+    fn macro_to_post_json_without_dn_test() {
+        let tenant = Tenant {
+            name: String::from("TenantName"),
+            bytes: 500,
+        };
+
+        assert!(matches!(
+            tenant.to_post_json(),
+            Err(crate::AciError::Query(_))
+        ));
+    }
+
+    crate::aci_struct!(
+        User,
+        "aaaUser",
+        {
+            name: String,
+            #[aci(rename = "accountStatus")]
+            account_status: super::EnabledDisabled,
+            #[aci(rename = "remoteUser")]
+            remote_user: super::TrueFalse,
+            #[aci(rename = "pwdLifeTime")]
+            pwd_life_time: i32,
+            expiration: Option<time::OffsetDateTime>,
+            #[aci(rename = "modTs")]
+            mod_ts: time::OffsetDateTime,
+            #[aci(rename = "rolesR")]
+            roles: Vec<String>,
+            load: f64,
+            bytes: u64,
+            phone: Option<String>,
+        }
+    );
+
+    #[test]
+    fn macro_field_types_test() {
+        let json_data = r#"
+    {
+        "aaaUser": {
+            "attributes": {
+                "name": "admin",
+                "accountStatus": "enabled",
+                "remoteUser": "false",
+                "pwdLifeTime": "-1",
+                "modTs": "2024-03-17T15:37:25.123+00:00",
+                "rolesR": "admin,ops, tenant-epg",
+                "load": "0.75",
+                "bytes": "500"
+            }
+        }
+    }"#;
+        let user: User = serde_json::from_str(json_data).expect("Failed to deserialize");
+        assert!(bool::from(user.account_status));
+        assert_eq!(user.remote_user, super::TrueFalse::False);
+        assert_eq!(user.pwd_life_time, -1);
+        assert_eq!(user.expiration, None);
+        assert_eq!(user.mod_ts.year(), 2024);
+        assert_eq!(user.mod_ts.millisecond(), 123);
+        assert_eq!(user.roles, vec!["admin", "ops", "tenant-epg"]);
+        assert_eq!(user.load, 0.75);
+        assert_eq!(user.bytes, 500);
+        assert_eq!(user.phone, None);
+
+        let value = serde_json::to_value(&user).unwrap();
+        let attributes = &value["aaaUser"]["attributes"];
+        assert_eq!(attributes["accountStatus"], "enabled");
+        assert_eq!(attributes["remoteUser"], "false");
+        assert_eq!(attributes["pwdLifeTime"], "-1");
+        assert_eq!(attributes["load"], "0.75");
+        assert_eq!(attributes["modTs"], "2024-03-17T15:37:25.123Z");
+        assert_eq!(attributes["rolesR"], "admin,ops,tenant-epg");
+        assert!(attributes.get("phone").is_none());
+    }
+
+    #[test]
+    fn macro_invalid_field_test() {
+        let json_data =
+            r#"{"aaaUser": {"attributes": {"name": "admin", "accountStatus": "maybe"}}}"#;
+        let error = serde_json::from_str::<User>(json_data).unwrap_err();
+        assert!(error.to_string().contains("Invalid Field: accountStatus"));
+
+        let json_data = r#"{"fvBD": {"attributes": {"name": "BD1", "arpFlood": "enabled"}}}"#;
+        let error = serde_json::from_str::<Bridge>(json_data).unwrap_err();
+        assert!(error.to_string().contains("Invalid Field: arpFlood"));
+
+        let json_data = r#"{"fvTenant": {"attributes": {"name": "TenantName", "bytes": "many"}}}"#;
+        let error = serde_json::from_str::<Tenant>(json_data).unwrap_err();
+        assert!(error.to_string().contains("Invalid Field: bytes"));
+    }
+
+    #[test]
+    fn macro_missing_field_test() {
+        let json_data = r#"{"fvTenant": {"attributes": {"name": "TenantName"}}}"#;
+        let error = serde_json::from_str::<Tenant>(json_data).unwrap_err();
+        assert!(error.to_string().contains("Missing Field: bytes"));
+    }
+
+    crate::aci_struct!(
+        /// A bridge domain with snake case fields
+        #[derive(Clone, PartialEq)]
+        Bridge,
+        "fvBD",
+        {
+            name: String,
+            #[aci(rename = "nameAlias")]
+            name_alias: Option<String>,
+            #[aci(rename = "unkMacUcastAct", default = String::from("proxy"))]
+            unknown_unicast: String,
+            #[aci(default)]
+            #[aci(rename = "epMoveDetectMode")]
+            ep_move_detect_mode: String,
+            #[aci(rename = "arpFlood")]
+            arp_flood: bool,
+        }
+    );
+
+    #[test]
+    fn macro_rename_and_default_test() {
+        let json_data = r#"
+    {
+        "fvBD": {
+            "attributes": {
+                "name": "BD1",
+                "nameAlias": "alias",
+                "arpFlood": "yes"
+            }
+        }
+    }"#;
+        let bd: Bridge = serde_json::from_str(json_data).expect("Failed to deserialize");
+        assert_eq!(bd.name_alias.as_deref(), Some("alias"));
+        assert_eq!(bd.unknown_unicast, "proxy");
+        assert_eq!(bd.ep_move_detect_mode, "");
+        assert!(bd.arp_flood);
+        assert!(bd == bd.clone());
+
+        let value = serde_json::to_value(&bd).unwrap();
+        let attributes = &value["fvBD"]["attributes"];
+        assert_eq!(attributes["nameAlias"], "alias");
+        assert_eq!(attributes["unkMacUcastAct"], "proxy");
+        assert_eq!(attributes["arpFlood"], "yes");
+        assert!(attributes.get("name_alias").is_none());
+
+        let json_data = r#"{"fvBD": {"attributes": {"name": "BD1", "unkMacUcastAct": "flood"}}}"#;
+        let error = serde_json::from_str::<Bridge>(json_data).unwrap_err();
+        assert!(error.to_string().contains("Missing Field: arpFlood"));
+    }
+
+    crate::aci_struct!(
+        Vrf,
+        "fvCtx",
+        {
+            name: String,
+        }
+    );
+
+    crate::aci_struct!(
+        TenantTree,
+        "fvTenant",
+        {
+            name: String,
+            #[aci(children)]
+            bds: Vec<Bridge>,
+            #[aci(children)]
+            vrfs: Vec<Vrf>,
+        }
+    );
+
+    #[test]
+    fn macro_children_test() {
+        let json_data = r#"
+    {
+        "fvTenant": {
+            "attributes": {"name": "A"},
+            "children": [
+                {"fvBD": {"attributes": {"name": "BD1", "arpFlood": "no"}}},
+                {"fvCtx": {"attributes": {"name": "VRF1"}}},
+                {"fvAp": {"attributes": {"name": "AP1"}}},
+                {"fvBD": {"attributes": {"name": "BD2", "arpFlood": "yes"}}}
+            ]
+        }
+    }"#;
+        let tenant: TenantTree = serde_json::from_str(json_data).expect("Failed to deserialize");
+        assert_eq!(tenant.name, "A");
+        assert_eq!(
+            tenant
+                .bds
+                .iter()
+                .map(|bd| bd.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["BD1", "BD2"]
+        );
+        assert_eq!(tenant.vrfs[0].name, "VRF1");
+
+        let value = serde_json::to_value(&tenant).unwrap();
+        assert!(value["fvTenant"]["attributes"].get("bds").is_none());
+        let children = value["fvTenant"]["children"].as_array().unwrap();
+        assert_eq!(children.len(), 3);
+        assert_eq!(children[2]["fvCtx"]["attributes"]["name"], "VRF1");
+
+        let json_data = r#"{"fvTenant": {"attributes": {"name": "A"}}}"#;
+        let tenant: TenantTree = serde_json::from_str(json_data).expect("Failed to deserialize");
+        assert!(tenant.bds.is_empty());
+        assert!(serde_json::to_value(&tenant).unwrap()["fvTenant"]
+            .get("children")
+            .is_none());
+
+        let json_data = r#"{"fvTenant": {"attributes": {"name": "A"}, "children": [{"fvBD": {"attributes": {}}}]}}"#;
+        let error = serde_json::from_str::<TenantTree>(json_data).unwrap_err();
+        assert!(error.to_string().contains("Invalid Child fvBD"));
+    }
+
+    crate::aci_enum!(PcEnfPref {
+        Enforced = "enforced",
+        Unenforced = "unenforced",
+    });
+
+    crate::aci_struct!(
+        EnforcedVrf,
+        "fvCtx",
+        {
+            name: String,
+            #[aci(rename = "pcEnfPref")]
+            pc_enf_pref: Option<PcEnfPref>,
+        }
+    );
+
+    #[test]
+    fn macro_enum_test() {
+        let json_data = r#"{"fvCtx": {"attributes": {"name": "VRF1", "pcEnfPref": "unenforced"}}}"#;
+        let vrf: EnforcedVrf = serde_json::from_str(json_data).expect("Failed to deserialize");
+        assert_eq!(vrf.pc_enf_pref, Some(PcEnfPref::Unenforced));
+        assert_eq!(
+            serde_json::to_value(&vrf).unwrap()["fvCtx"]["attributes"]["pcEnfPref"],
+            "unenforced"
+        );
+        assert_eq!(PcEnfPref::Enforced.to_string(), "enforced");
+
+        let json_data = r#"{"fvCtx": {"attributes": {"name": "VRF1", "pcEnfPref": "on"}}}"#;
+        let error = serde_json::from_str::<EnforcedVrf>(json_data).unwrap_err();
+        assert!(error.to_string().contains("Invalid Field: pcEnfPref"));
+    }
+
+    crate::aci_enum!(UnkMacUcastAct {
+        Proxy = "proxy",
+        Flood = "flood",
+        _ => Other,
+    });
+
+    #[test]
+    fn macro_enum_fallback_test() {
+        assert_eq!(Some(UnkMacUcastAct::Flood), "flood".parse().ok());
+        let value = "drop".parse::<UnkMacUcastAct>().unwrap();
+        assert_eq!(value, UnkMacUcastAct::Other(String::from("drop")));
+        assert_eq!(value.as_str(), "drop");
+        assert_eq!(UnkMacUcastAct::Proxy.to_string(), "proxy");
+    }
+
+    #[test]
+    fn macro_dn_test() {
+        let json_data = r#"{"fvAEPg": {"attributes": {"dn": "uni/tn-A/ap-B/epg-C"}}}"#;
+        let epg: Epg = serde_json::from_str(json_data).expect("Failed to deserialize");
+        assert_eq!(epg.dn.prefix(), "epg");
+        assert_eq!(epg.dn.parent().unwrap().to_string(), "uni/tn-A/ap-B");
+
+        let json_data = r#"{"fvAEPg": {"attributes": {"dn": "uni//epg-C"}}}"#;
+        assert!(serde_json::from_str::<Epg>(json_data).is_err());
+    }
 }
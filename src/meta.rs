@@ -0,0 +1,244 @@
+//! The object model of the APIC, as served at `/acimeta/aci-meta.json`.
+//!
+//! Only the parts needed to generate and check objects are read: the
+//! properties of the classes with their valid values, the RN format, the
+//! containment and the target of relations.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+#[derive(Debug, thiserror::Error)]
+pub enum MetaError {
+    #[error("Could not read {path}: {source}")]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Could not parse {path}: {message}")]
+    Parse { path: PathBuf, message: String },
+    #[error("Unknown class: {0:?}")]
+    UnknownClass(String),
+}
+
+/// The classes of a saved `aci-meta.json`, e.g.
+///
+/// ```no_run
+/// use rustyaci::meta::AciMeta;
+///
+/// let meta = AciMeta::from_file("aci-meta.json").unwrap();
+/// let tenant = meta.class("fvTenant").unwrap();
+/// assert_eq!("tn-{name}", tenant.rn_format);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct AciMeta {
+    #[serde(rename = "aciClassMetas", alias = "classes")]
+    pub classes: BTreeMap<String, ClassMeta>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ClassMeta {
+    pub label: String,
+    pub is_abstract: bool,
+    pub is_configurable: bool,
+    /// The relative name with the naming properties in braces, e.g.
+    /// `tn-{name}` or `subnet-[{ip}]`.
+    pub rn_format: String,
+    pub identified_by: Vec<String>,
+    /// The classes of the possible parents.
+    #[serde(deserialize_with = "class_names")]
+    pub contained_by: BTreeSet<String>,
+    /// The classes of the possible children.
+    #[serde(deserialize_with = "class_names")]
+    pub contains: BTreeSet<String>,
+    pub properties: BTreeMap<String, PropertyMeta>,
+    /// Set for relation classes like `fvRsCtx`.
+    pub relation_info: Option<RelationInfo>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PropertyMeta {
+    pub label: String,
+    pub is_configurable: bool,
+    pub is_naming: bool,
+    /// e.g. `string`, `number`, `enum` or `bitmask`
+    pub uitype: String,
+    pub valid_values: Vec<ValidValue>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ValidValue {
+    /// The value used in the REST API, e.g. `enforced`.
+    pub local_name: String,
+    /// The internal value, e.g. `1`.
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RelationInfo {
+    /// e.g. `named` or `explicit`
+    #[serde(rename = "type")]
+    pub relation_type: String,
+    pub from_mo: String,
+    /// The class the relation points to, e.g. `fvCtx` for `fvRsCtx`.
+    pub to_mo: String,
+}
+
+/// `containedBy` and `contains` are objects with the classes as keys, lists
+/// are accepted as well.
+fn class_names<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeSet<String>, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::Object(classes) => Ok(classes.into_iter().map(|(class, _)| class).collect()),
+        Value::Array(classes) => Ok(classes
+            .into_iter()
+            .filter_map(|class| class.as_str().map(String::from))
+            .collect()),
+        _ => Ok(BTreeSet::new()),
+    }
+}
+
+impl AciMeta {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, MetaError> {
+        let path = path.as_ref();
+        let data = std::fs::read_to_string(path).map_err(|source| MetaError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        serde_json::from_str(&data).map_err(|error| MetaError::Parse {
+            path: path.to_path_buf(),
+            message: error.to_string(),
+        })
+    }
+
+    pub fn class(&self, class_name: &str) -> Result<&ClassMeta, MetaError> {
+        self.classes
+            .get(class_name)
+            .ok_or_else(|| MetaError::UnknownClass(class_name.to_string()))
+    }
+}
+
+impl ClassMeta {
+    /// The properties in the braces of the RN format, in order.
+    pub fn naming_properties(&self) -> Vec<&str> {
+        self.rn_format
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    /// The properties that can be posted, naming properties first.
+    pub fn configurable_properties(&self) -> Vec<(&str, &PropertyMeta)> {
+        let naming = self.naming_properties();
+        let mut properties = naming
+            .iter()
+            .filter_map(|name| self.properties.get_key_value(*name))
+            .map(|(name, property)| (name.as_str(), property))
+            .collect::<Vec<_>>();
+        properties.extend(
+            self.properties
+                .iter()
+                .filter(|(name, property)| {
+                    property.is_configurable
+                        && !naming.contains(&name.as_str())
+                        && !matches!(name.as_str(), "dn" | "rn" | "status" | "childAction")
+                })
+                .map(|(name, property)| (name.as_str(), property)),
+        );
+        properties
+    }
+}
+
+impl PropertyMeta {
+    /// The valid values of an enum property (`uitype` `enum`). Empty for
+    /// bitmasks and free text, but also for numbers like `dFromPort` of
+    /// `vzEntry`, whose valid values are only named constants.
+    pub fn enum_values(&self) -> Vec<&str> {
+        if self.uitype != "enum" {
+            return Vec::new();
+        }
+        let mut values = Vec::new();
+        for value in &self.valid_values {
+            if !value.local_name.is_empty() && !values.contains(&value.local_name.as_str()) {
+                values.push(value.local_name.as_str());
+            }
+        }
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta() -> AciMeta {
+        AciMeta::from_file("tests/json/meta/aci-meta.json").unwrap()
+    }
+
+    #[test]
+    fn from_file() {
+        let meta = meta();
+        let bd = meta.class("fvBD").unwrap();
+
+        assert_eq!("Bridge Domain", bd.label);
+        assert_eq!("BD-{name}", bd.rn_format);
+        assert!(bd.contained_by.contains("fvTenant"));
+        assert!(bd.contains.contains("fvSubnet"));
+        assert_eq!(
+            vec!["proxy", "flood"],
+            bd.properties["unkMacUcastAct"].enum_values()
+        );
+
+        let rs_ctx = meta.class("fvRsCtx").unwrap();
+        assert_eq!("fvCtx", rs_ctx.relation_info.as_ref().unwrap().to_mo);
+    }
+
+    #[test]
+    fn from_file_errors() {
+        assert!(matches!(
+            AciMeta::from_file("tests/json/meta/missing.json"),
+            Err(MetaError::Read { .. })
+        ));
+        assert!(matches!(
+            AciMeta::from_file("tests/config/fabrics.toml"),
+            Err(MetaError::Parse { .. })
+        ));
+        assert!(matches!(
+            meta().class("fvUnknown"),
+            Err(MetaError::UnknownClass(_))
+        ));
+    }
+
+    #[test]
+    fn properties() {
+        let meta = meta();
+        let subnet = meta.class("fvSubnet").unwrap();
+
+        assert_eq!(vec!["ip"], subnet.naming_properties());
+        assert_eq!(
+            vec!["ip", "ctrl", "descr", "scope"],
+            subnet
+                .configurable_properties()
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>()
+        );
+        assert!(subnet.properties["scope"].enum_values().is_empty());
+        let entry = meta.class("vzEntry").unwrap();
+        assert!(entry.properties["dFromPort"].enum_values().is_empty());
+        assert_eq!(
+            vec!["unspecified", "ip", "arp"],
+            entry.properties["etherT"].enum_values()
+        );
+        assert!(meta.class("polUni").unwrap().naming_properties().is_empty());
+    }
+}
@@ -0,0 +1,250 @@
+use std::{fmt, str::FromStr};
+
+use serde::{
+    de::{self, MapAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use serde_json::{Map, Value};
+
+use crate::dn::{Dn, DnError};
+
+/// The `status` attribute of an object posted to the APIC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoStatus {
+    Created,
+    Modified,
+    Deleted,
+    /// Creates the object or updates it if it already exists.
+    CreatedModified,
+}
+
+impl MoStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MoStatus::Created => "created",
+            MoStatus::Modified => "modified",
+            MoStatus::Deleted => "deleted",
+            MoStatus::CreatedModified => "created,modified",
+        }
+    }
+}
+
+impl FromStr for MoStatus {
+    type Err = String;
+
+    fn from_str(status: &str) -> Result<Self, Self::Err> {
+        match status {
+            "created" => Ok(MoStatus::Created),
+            "modified" => Ok(MoStatus::Modified),
+            "deleted" => Ok(MoStatus::Deleted),
+            "created,modified" => Ok(MoStatus::CreatedModified),
+            _ => Err(format!("unknown status {status:?}")),
+        }
+    }
+}
+
+/// A managed object with its attributes and children, serialized as
+/// `{"<class>": {"attributes": {..}, "children": [..]}}`, e.g.
+///
+/// ```
+/// use rustyaci::mo::{ManagedObject, MoStatus};
+///
+/// let tenant = ManagedObject::new("fvTenant")
+///     .dn("uni/tn-A")
+///     .attribute("name", "A")
+///     .status(MoStatus::CreatedModified)
+///     .child(ManagedObject::new("fvBD").attribute("name", "BD1"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManagedObject {
+    pub class_name: String,
+    pub attributes: Map<String, Value>,
+    pub children: Vec<ManagedObject>,
+    pub status: Option<MoStatus>,
+}
+
+impl ManagedObject {
+    pub fn new(class_name: impl Into<String>) -> Self {
+        ManagedObject {
+            class_name: class_name.into(),
+            attributes: Map::new(),
+            children: Vec::new(),
+            status: None,
+        }
+    }
+
+    pub fn attribute(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.attributes
+            .insert(name.into(), Value::String(value.into()));
+        self
+    }
+
+    pub fn dn(self, dn: impl Into<String>) -> Self {
+        self.attribute("dn", dn)
+    }
+
+    pub fn child(mut self, child: ManagedObject) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn status(mut self, status: MoStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn get_attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name)?.as_str()
+    }
+
+    /// The parsed `dn` attribute, `None` if the object has none.
+    pub fn get_dn(&self) -> Option<Result<Dn, DnError>> {
+        self.get_attribute("dn").map(str::parse)
+    }
+}
+
+impl Serialize for ManagedObject {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut attributes = self.attributes.clone();
+        if let Some(status) = self.status {
+            attributes.insert("status".to_string(), Value::from(status.as_str()));
+        }
+        let mut body = Map::new();
+        body.insert("attributes".to_string(), Value::Object(attributes));
+        if !self.children.is_empty() {
+            let children =
+                serde_json::to_value(&self.children).map_err(serde::ser::Error::custom)?;
+            body.insert("children".to_string(), children);
+        }
+
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(&self.class_name, &body)?;
+        map.end()
+    }
+}
+
+#[derive(Deserialize)]
+struct Body {
+    #[serde(default)]
+    attributes: Map<String, Value>,
+    #[serde(default)]
+    children: Vec<ManagedObject>,
+}
+
+impl<'de> Deserialize<'de> for ManagedObject {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(ManagedObjectVisitor)
+    }
+}
+
+struct ManagedObjectVisitor;
+
+impl<'de> Visitor<'de> for ManagedObjectVisitor {
+    type Value = ManagedObject;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an object with the class name as its only key")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let Some((class_name, mut body)) = map.next_entry::<String, Body>()? else {
+            return Err(de::Error::invalid_length(0, &self));
+        };
+        if map.next_key::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(2, &self));
+        }
+
+        // Objects read from the APIC carry an empty status
+        let status = match body.attributes.remove("status") {
+            Some(Value::String(status)) if !status.is_empty() => {
+                Some(status.parse().map_err(de::Error::custom)?)
+            }
+            _ => None,
+        };
+
+        Ok(ManagedObject {
+            class_name,
+            attributes: body.attributes,
+            children: body.children,
+            status,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn serialize() {
+        let epg = ManagedObject::new("fvAEPg")
+            .dn("uni/tn-TEST/ap-TEST/epg-TEST")
+            .attribute("name", "TEST");
+        let expected: Value =
+            serde_json::from_str(&fs::read_to_string("tests/json/post/epg-TEST.json").unwrap())
+                .unwrap();
+
+        assert_eq!(expected, serde_json::to_value(&epg).unwrap());
+    }
+
+    #[test]
+    fn serialize_children_and_status() {
+        let tenant = ManagedObject::new("fvTenant")
+            .dn("uni/tn-A")
+            .status(MoStatus::CreatedModified)
+            .child(
+                ManagedObject::new("fvBD")
+                    .attribute("name", "BD1")
+                    .status(MoStatus::Deleted),
+            );
+
+        assert_eq!(
+            serde_json::json!({
+                "fvTenant": {
+                    "attributes": {"dn": "uni/tn-A", "status": "created,modified"},
+                    "children": [
+                        {"fvBD": {"attributes": {"name": "BD1", "status": "deleted"}}}
+                    ]
+                }
+            }),
+            serde_json::to_value(&tenant).unwrap()
+        );
+    }
+
+    #[test]
+    fn deserialize() {
+        let json = r#"{
+            "fvTenant": {
+                "attributes": {"dn": "uni/tn-A", "name": "A", "status": ""},
+                "children": [{"fvBD": {"attributes": {"name": "BD1", "status": "modified"}}}]
+            }
+        }"#;
+        let tenant: ManagedObject = serde_json::from_str(json).unwrap();
+
+        assert_eq!("fvTenant", tenant.class_name);
+        assert_eq!(Some("A"), tenant.get_attribute("name"));
+        assert_eq!(Some(Ok("uni/tn-A".parse().unwrap())), tenant.get_dn());
+        assert_eq!(None, tenant.status);
+        assert_eq!(Some(MoStatus::Modified), tenant.children[0].status);
+        assert_eq!(
+            tenant,
+            serde_json::from_value(serde_json::to_value(&tenant).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid() {
+        assert!(serde_json::from_str::<ManagedObject>("{}").is_err());
+        assert!(serde_json::from_str::<ManagedObject>(
+            r#"{"fvTenant": {"attributes": {}}, "fvBD": {"attributes": {}}}"#
+        )
+        .is_err());
+        assert!(serde_json::from_str::<ManagedObject>(
+            r#"{"fvTenant": {"attributes": {"status": "unknown"}}}"#
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,327 @@
+//! Typed models of the core tenant objects.
+//!
+//! Only `name` (or the naming attribute of relations and subnets) is
+//! required, the other attributes are `None` if the APIC doesn't return them
+//! and are left out when posting, so the models can be used for partial
+//! updates, e.g.
+//!
+//! ```
+//! use rustyaci::models::{BridgeDomain, Subnet, Tenant};
+//!
+//! let tenant = Tenant {
+//!     dn: Some("uni/tn-A".parse().unwrap()),
+//!     name: String::from("A"),
+//!     bds: vec![BridgeDomain {
+//!         name: String::from("BD1"),
+//!         subnets: vec![Subnet {
+//!             ip: String::from("10.0.0.1/24"),
+//!             ..Default::default()
+//!         }],
+//!         ..Default::default()
+//!     }],
+//!     ..Default::default()
+//! };
+//! ```
+
+use crate::{dn::Dn, AciObject};
+
+/// A tenant (`fvTenant`).
+#[derive(Debug, Clone, Default, PartialEq, AciObject)]
+#[aci(class = "fvTenant")]
+pub struct Tenant {
+    pub dn: Option<Dn>,
+    pub name: String,
+    pub descr: Option<String>,
+    #[aci(rename = "nameAlias")]
+    pub name_alias: Option<String>,
+    #[aci(children)]
+    pub vrfs: Vec<Vrf>,
+    #[aci(children)]
+    pub bds: Vec<BridgeDomain>,
+    #[aci(children)]
+    pub aps: Vec<Ap>,
+    #[aci(children)]
+    pub contracts: Vec<Contract>,
+    #[aci(children)]
+    pub filters: Vec<Filter>,
+}
+
+/// A VRF (`fvCtx`).
+#[derive(Debug, Clone, Default, PartialEq, AciObject)]
+#[aci(class = "fvCtx")]
+pub struct Vrf {
+    pub dn: Option<Dn>,
+    pub name: String,
+    pub descr: Option<String>,
+    #[aci(rename = "nameAlias")]
+    pub name_alias: Option<String>,
+    /// `enforced` or `unenforced`
+    #[aci(rename = "pcEnfPref")]
+    pub pc_enf_pref: Option<String>,
+    /// `ingress` or `egress`
+    #[aci(rename = "pcEnfDir")]
+    pub pc_enf_dir: Option<String>,
+}
+
+/// A bridge domain (`fvBD`).
+#[derive(Debug, Clone, Default, PartialEq, AciObject)]
+#[aci(class = "fvBD")]
+pub struct BridgeDomain {
+    pub dn: Option<Dn>,
+    pub name: String,
+    pub descr: Option<String>,
+    #[aci(rename = "nameAlias")]
+    pub name_alias: Option<String>,
+    #[aci(rename = "arpFlood")]
+    pub arp_flood: Option<bool>,
+    #[aci(rename = "unicastRoute")]
+    pub unicast_route: Option<bool>,
+    /// `proxy` or `flood`
+    #[aci(rename = "unkMacUcastAct")]
+    pub unk_mac_ucast_act: Option<String>,
+    pub mac: Option<String>,
+    #[aci(children)]
+    pub subnets: Vec<Subnet>,
+    #[aci(children)]
+    pub rs_ctx: Vec<RsCtx>,
+}
+
+/// A subnet of a bridge domain or EPG (`fvSubnet`), named by its gateway
+/// address, e.g. `10.0.0.1/24`.
+#[derive(Debug, Clone, Default, PartialEq, AciObject)]
+#[aci(class = "fvSubnet")]
+pub struct Subnet {
+    pub dn: Option<Dn>,
+    pub ip: String,
+    pub descr: Option<String>,
+    /// e.g. `private`, `public,shared`
+    pub scope: Option<Vec<String>>,
+    pub ctrl: Option<Vec<String>>,
+}
+
+/// An application profile (`fvAp`).
+#[derive(Debug, Clone, Default, PartialEq, AciObject)]
+#[aci(class = "fvAp")]
+pub struct Ap {
+    pub dn: Option<Dn>,
+    pub name: String,
+    pub descr: Option<String>,
+    #[aci(rename = "nameAlias")]
+    pub name_alias: Option<String>,
+    #[aci(children)]
+    pub epgs: Vec<Epg>,
+}
+
+/// An endpoint group (`fvAEPg`).
+#[derive(Debug, Clone, Default, PartialEq, AciObject)]
+#[aci(class = "fvAEPg")]
+pub struct Epg {
+    pub dn: Option<Dn>,
+    pub name: String,
+    pub descr: Option<String>,
+    #[aci(rename = "nameAlias")]
+    pub name_alias: Option<String>,
+    /// `enforced` or `unenforced`
+    #[aci(rename = "pcEnfPref")]
+    pub pc_enf_pref: Option<String>,
+    /// `include` or `exclude`
+    #[aci(rename = "prefGrMemb")]
+    pub pref_gr_memb: Option<String>,
+    #[aci(children)]
+    pub rs_bd: Vec<RsBd>,
+    #[aci(children)]
+    pub rs_cons: Vec<RsCons>,
+    #[aci(children)]
+    pub rs_prov: Vec<RsProv>,
+    #[aci(children)]
+    pub subnets: Vec<Subnet>,
+}
+
+/// A contract (`vzBrCP`).
+#[derive(Debug, Clone, Default, PartialEq, AciObject)]
+#[aci(class = "vzBrCP")]
+pub struct Contract {
+    pub dn: Option<Dn>,
+    pub name: String,
+    pub descr: Option<String>,
+    #[aci(rename = "nameAlias")]
+    pub name_alias: Option<String>,
+    /// `context`, `tenant`, `application-profile` or `global`
+    pub scope: Option<String>,
+    pub prio: Option<String>,
+    #[aci(children)]
+    pub subjects: Vec<Subject>,
+}
+
+/// A subject of a contract (`vzSubj`).
+#[derive(Debug, Clone, Default, PartialEq, AciObject)]
+#[aci(class = "vzSubj")]
+pub struct Subject {
+    pub dn: Option<Dn>,
+    pub name: String,
+    pub descr: Option<String>,
+    #[aci(rename = "nameAlias")]
+    pub name_alias: Option<String>,
+    #[aci(rename = "revFltPorts")]
+    pub rev_flt_ports: Option<bool>,
+    #[aci(children)]
+    pub filters: Vec<RsSubjFiltAtt>,
+}
+
+/// A filter (`vzFilter`).
+#[derive(Debug, Clone, Default, PartialEq, AciObject)]
+#[aci(class = "vzFilter")]
+pub struct Filter {
+    pub dn: Option<Dn>,
+    pub name: String,
+    pub descr: Option<String>,
+    #[aci(rename = "nameAlias")]
+    pub name_alias: Option<String>,
+    #[aci(children)]
+    pub entries: Vec<Entry>,
+}
+
+/// An entry of a filter (`vzEntry`).
+#[derive(Debug, Clone, Default, PartialEq, AciObject)]
+#[aci(class = "vzEntry")]
+pub struct Entry {
+    pub dn: Option<Dn>,
+    pub name: String,
+    pub descr: Option<String>,
+    /// e.g. `ip`, `arp` or `unspecified`
+    #[aci(rename = "etherT")]
+    pub ether_t: Option<String>,
+    /// e.g. `tcp`, `udp` or `icmp`
+    pub prot: Option<String>,
+    #[aci(rename = "sFromPort")]
+    pub s_from_port: Option<String>,
+    #[aci(rename = "sToPort")]
+    pub s_to_port: Option<String>,
+    #[aci(rename = "dFromPort")]
+    pub d_from_port: Option<String>,
+    #[aci(rename = "dToPort")]
+    pub d_to_port: Option<String>,
+    pub stateful: Option<bool>,
+}
+
+/// The VRF of a bridge domain (`fvRsCtx`).
+#[derive(Debug, Clone, Default, PartialEq, AciObject)]
+#[aci(class = "fvRsCtx")]
+pub struct RsCtx {
+    pub dn: Option<Dn>,
+    #[aci(rename = "tnFvCtxName")]
+    pub tn_fv_ctx_name: String,
+}
+
+/// The bridge domain of an EPG (`fvRsBd`).
+#[derive(Debug, Clone, Default, PartialEq, AciObject)]
+#[aci(class = "fvRsBd")]
+pub struct RsBd {
+    pub dn: Option<Dn>,
+    #[aci(rename = "tnFvBDName")]
+    pub tn_fv_bd_name: String,
+}
+
+/// A contract consumed by an EPG (`fvRsCons`).
+#[derive(Debug, Clone, Default, PartialEq, AciObject)]
+#[aci(class = "fvRsCons")]
+pub struct RsCons {
+    pub dn: Option<Dn>,
+    #[aci(rename = "tnVzBrCPName")]
+    pub tn_vz_br_cp_name: String,
+}
+
+/// A contract provided by an EPG (`fvRsProv`).
+#[derive(Debug, Clone, Default, PartialEq, AciObject)]
+#[aci(class = "fvRsProv")]
+pub struct RsProv {
+    pub dn: Option<Dn>,
+    #[aci(rename = "tnVzBrCPName")]
+    pub tn_vz_br_cp_name: String,
+}
+
+/// A filter of a contract subject (`vzRsSubjFiltAtt`).
+#[derive(Debug, Clone, Default, PartialEq, AciObject)]
+#[aci(class = "vzRsSubjFiltAtt")]
+pub struct RsSubjFiltAtt {
+    pub dn: Option<Dn>,
+    #[aci(rename = "tnVzFilterName")]
+    pub tn_vz_filter_name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use serde_json::Value;
+
+    use super::*;
+
+    fn tenant_json() -> Value {
+        let data = fs::read_to_string("tests/json/models/tenant.json").unwrap();
+        serde_json::from_str(&data).unwrap()
+    }
+
+    #[test]
+    fn deserialize_tenant_tree() {
+        let tenant: Tenant = serde_json::from_value(tenant_json()).unwrap();
+
+        assert_eq!("A", tenant.name);
+        assert_eq!(Some("uni/tn-A".parse().unwrap()), tenant.dn());
+        assert_eq!(Some(String::from("enforced")), tenant.vrfs[0].pc_enf_pref);
+
+        let bd = &tenant.bds[0];
+        assert_eq!(Some(true), bd.arp_flood);
+        assert_eq!(Some(false), bd.unicast_route);
+        assert_eq!("10.0.0.1/24", bd.subnets[0].ip);
+        assert_eq!(
+            Some(vec![String::from("public"), String::from("shared")]),
+            bd.subnets[0].scope
+        );
+        assert_eq!("VRF1", bd.rs_ctx[0].tn_fv_ctx_name);
+
+        let epg = &tenant.aps[0].epgs[0];
+        assert_eq!("BD1", epg.rs_bd[0].tn_fv_bd_name);
+        assert_eq!("web", epg.rs_prov[0].tn_vz_br_cp_name);
+        assert!(epg.rs_cons.is_empty());
+
+        let subject = &tenant.contracts[0].subjects[0];
+        assert_eq!(Some(true), subject.rev_flt_ports);
+        assert_eq!("http", subject.filters[0].tn_vz_filter_name);
+
+        let entry = &tenant.filters[0].entries[0];
+        assert_eq!(Some(String::from("tcp")), entry.prot);
+        assert_eq!(Some(String::from("80")), entry.d_from_port);
+        assert_eq!(Some(false), entry.stateful);
+    }
+
+    #[test]
+    fn serialize_tenant_tree() {
+        let tenant: Tenant = serde_json::from_value(tenant_json()).unwrap();
+
+        assert_eq!(tenant_json(), serde_json::to_value(&tenant).unwrap());
+    }
+
+    #[test]
+    fn serialize_partial() {
+        let epg = Epg {
+            name: String::from("WEB"),
+            rs_bd: vec![RsBd {
+                tn_fv_bd_name: String::from("BD1"),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serde_json::json!({
+                "fvAEPg": {
+                    "attributes": {"name": "WEB"},
+                    "children": [{"fvRsBd": {"attributes": {"tnFvBDName": "BD1"}}}]
+                }
+            }),
+            serde_json::to_value(&epg).unwrap()
+        );
+        assert_eq!("class/fvAEPg.json", Epg::class_query().build().unwrap());
+    }
+}
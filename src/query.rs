@@ -0,0 +1,533 @@
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+
+use crate::filter::Filter;
+
+/// Characters escaped in the DN of a managed object query. Slashes and the
+/// brackets used by RNs such as `pathep-[eth1/1]` are kept as they are.
+const DN_ENCODE_SET: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+/// Characters escaped in the values of query options.
+const VALUE_ENCODE_SET: &AsciiSet = &DN_ENCODE_SET.add(b'&').add(b'+').add(b'=');
+
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum QueryError {
+    #[error("Invalid class name: {0:?}")]
+    InvalidClass(String),
+    #[error("Invalid distinguished name: {0:?}")]
+    InvalidDn(String),
+    #[error("Invalid property: {0:?}")]
+    InvalidProperty(String),
+    #[error("Invalid filter: {0}")]
+    InvalidFilter(String),
+    #[error("{0} requires {1}")]
+    InvalidCombination(&'static str, &'static str),
+}
+
+/// Scope of the objects returned by a query (`query-target`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryTarget {
+    /// Only the object itself (`self`).
+    SelfOnly,
+    Children,
+    Subtree,
+}
+
+impl QueryTarget {
+    fn as_str(&self) -> &'static str {
+        match self {
+            QueryTarget::SelfOnly => "self",
+            QueryTarget::Children => "children",
+            QueryTarget::Subtree => "subtree",
+        }
+    }
+}
+
+/// Which part of the subtree is included in the response (`rsp-subtree`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RspSubtree {
+    No,
+    Children,
+    Full,
+}
+
+impl RspSubtree {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RspSubtree::No => "no",
+            RspSubtree::Children => "children",
+            RspSubtree::Full => "full",
+        }
+    }
+}
+
+/// Additional objects included in the response (`rsp-subtree-include`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RspSubtreeInclude {
+    AuditLogs,
+    EventLogs,
+    Faults,
+    FaultRecords,
+    Health,
+    HealthRecords,
+    Relations,
+    Stats,
+    Tasks,
+    Count,
+    NoScoped,
+    Required,
+    Subtree,
+}
+
+impl RspSubtreeInclude {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RspSubtreeInclude::AuditLogs => "audit-logs",
+            RspSubtreeInclude::EventLogs => "event-logs",
+            RspSubtreeInclude::Faults => "faults",
+            RspSubtreeInclude::FaultRecords => "fault-records",
+            RspSubtreeInclude::Health => "health",
+            RspSubtreeInclude::HealthRecords => "health-records",
+            RspSubtreeInclude::Relations => "relations",
+            RspSubtreeInclude::Stats => "stats",
+            RspSubtreeInclude::Tasks => "tasks",
+            RspSubtreeInclude::Count => "count",
+            RspSubtreeInclude::NoScoped => "no-scoped",
+            RspSubtreeInclude::Required => "required",
+            RspSubtreeInclude::Subtree => "subtree",
+        }
+    }
+}
+
+/// Which properties of the objects are returned (`rsp-prop-include`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RspPropInclude {
+    All,
+    NamingOnly,
+    ConfigOnly,
+}
+
+impl RspPropInclude {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RspPropInclude::All => "all",
+            RspPropInclude::NamingOnly => "naming-only",
+            RspPropInclude::ConfigOnly => "config-only",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    Asc,
+    Desc,
+}
+
+impl Order {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Order::Asc => "asc",
+            Order::Desc => "desc",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Target {
+    Class(String),
+    Mo(String),
+}
+
+/// Builder for the URI of a class or managed object query, as accepted by
+/// [`ACI::get`](crate::ACI::get) and [`ACI::get_json`](crate::ACI::get_json).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    target: Target,
+    query_target: Option<QueryTarget>,
+    target_subtree_class: Vec<String>,
+    query_target_filter: Option<Filter>,
+    rsp_subtree: Option<RspSubtree>,
+    rsp_subtree_class: Vec<String>,
+    rsp_subtree_filter: Option<Filter>,
+    rsp_subtree_include: Vec<RspSubtreeInclude>,
+    rsp_prop_include: Option<RspPropInclude>,
+    order_by: Vec<(String, Order)>,
+}
+
+impl Query {
+    fn new(target: Target) -> Self {
+        Query {
+            target,
+            query_target: None,
+            target_subtree_class: Vec::new(),
+            query_target_filter: None,
+            rsp_subtree: None,
+            rsp_subtree_class: Vec::new(),
+            rsp_subtree_filter: None,
+            rsp_subtree_include: Vec::new(),
+            rsp_prop_include: None,
+            order_by: Vec::new(),
+        }
+    }
+
+    /// Query all objects of a class, e.g. `fvAEPg`.
+    pub fn class(class: impl Into<String>) -> Self {
+        Query::new(Target::Class(class.into()))
+    }
+
+    /// Query the managed object with the given DN, e.g. `uni/tn-common`.
+    pub fn mo(dn: impl Into<String>) -> Self {
+        Query::new(Target::Mo(dn.into()))
+    }
+
+    pub fn query_target(mut self, query_target: QueryTarget) -> Self {
+        self.query_target = Some(query_target);
+        self
+    }
+
+    /// Restricts the children or subtree of the query to a class. Can be
+    /// called multiple times to include several classes.
+    pub fn target_subtree_class(mut self, class: impl Into<String>) -> Self {
+        self.target_subtree_class.push(class.into());
+        self
+    }
+
+    /// Only returns the objects that match the filter.
+    pub fn query_target_filter(mut self, filter: Filter) -> Self {
+        self.query_target_filter = Some(filter);
+        self
+    }
+
+    pub fn rsp_subtree(mut self, rsp_subtree: RspSubtree) -> Self {
+        self.rsp_subtree = Some(rsp_subtree);
+        self
+    }
+
+    /// Restricts the subtree in the response to a class. Can be called
+    /// multiple times to include several classes.
+    pub fn rsp_subtree_class(mut self, class: impl Into<String>) -> Self {
+        self.rsp_subtree_class.push(class.into());
+        self
+    }
+
+    /// Only includes the subtree objects that match the filter.
+    pub fn rsp_subtree_filter(mut self, filter: Filter) -> Self {
+        self.rsp_subtree_filter = Some(filter);
+        self
+    }
+
+    pub fn rsp_subtree_include(mut self, include: RspSubtreeInclude) -> Self {
+        self.rsp_subtree_include.push(include);
+        self
+    }
+
+    pub fn rsp_prop_include(mut self, rsp_prop_include: RspPropInclude) -> Self {
+        self.rsp_prop_include = Some(rsp_prop_include);
+        self
+    }
+
+    /// Sorts the result by a property in the form `class.property`, e.g.
+    /// `fvTenant.name`. Can be called multiple times.
+    pub fn order_by(mut self, property: impl Into<String>, order: Order) -> Self {
+        self.order_by.push((property.into(), order));
+        self
+    }
+
+    /// Validates the query and renders it to the URI used with
+    /// [`ACI::get`](crate::ACI::get).
+    pub fn build(&self) -> Result<String, QueryError> {
+        let mut uri = match &self.target {
+            Target::Class(class) => {
+                validate_class(class)?;
+                format!("class/{class}.json")
+            }
+            Target::Mo(dn) => {
+                if dn.is_empty() || dn.starts_with('/') || dn.ends_with('/') {
+                    return Err(QueryError::InvalidDn(dn.clone()));
+                }
+                format!("mo/{}.json", utf8_percent_encode(dn, DN_ENCODE_SET))
+            }
+        };
+
+        let options = self.options()?;
+        for (index, (key, value)) in options.iter().enumerate() {
+            let separator = if index == 0 { '?' } else { '&' };
+            let value = utf8_percent_encode(value, VALUE_ENCODE_SET);
+            uri.push_str(&format!("{separator}{key}={value}"));
+        }
+
+        Ok(uri)
+    }
+
+    fn options(&self) -> Result<Vec<(&'static str, String)>, QueryError> {
+        let mut options = Vec::new();
+
+        if let Some(query_target) = self.query_target {
+            options.push(("query-target", query_target.as_str().to_string()));
+        }
+        if !self.target_subtree_class.is_empty() {
+            if !matches!(
+                self.query_target,
+                Some(QueryTarget::Children | QueryTarget::Subtree)
+            ) {
+                return Err(QueryError::InvalidCombination(
+                    "target-subtree-class",
+                    "query-target children or subtree",
+                ));
+            }
+            for class in &self.target_subtree_class {
+                validate_class(class)?;
+            }
+            options.push(("target-subtree-class", self.target_subtree_class.join(",")));
+        }
+        if let Some(filter) = &self.query_target_filter {
+            filter.validate()?;
+            options.push(("query-target-filter", filter.to_string()));
+        }
+        if let Some(rsp_subtree) = self.rsp_subtree {
+            options.push(("rsp-subtree", rsp_subtree.as_str().to_string()));
+        }
+        if !self.rsp_subtree_class.is_empty() {
+            if !matches!(
+                self.rsp_subtree,
+                Some(RspSubtree::Children | RspSubtree::Full)
+            ) {
+                return Err(QueryError::InvalidCombination(
+                    "rsp-subtree-class",
+                    "rsp-subtree children or full",
+                ));
+            }
+            for class in &self.rsp_subtree_class {
+                validate_class(class)?;
+            }
+            options.push(("rsp-subtree-class", self.rsp_subtree_class.join(",")));
+        }
+        if let Some(filter) = &self.rsp_subtree_filter {
+            if !matches!(
+                self.rsp_subtree,
+                Some(RspSubtree::Children | RspSubtree::Full)
+            ) {
+                return Err(QueryError::InvalidCombination(
+                    "rsp-subtree-filter",
+                    "rsp-subtree children or full",
+                ));
+            }
+            filter.validate()?;
+            options.push(("rsp-subtree-filter", filter.to_string()));
+        }
+        if !self.rsp_subtree_include.is_empty() {
+            let include = self
+                .rsp_subtree_include
+                .iter()
+                .map(RspSubtreeInclude::as_str)
+                .collect::<Vec<_>>();
+            options.push(("rsp-subtree-include", include.join(",")));
+        }
+        if let Some(rsp_prop_include) = self.rsp_prop_include {
+            options.push(("rsp-prop-include", rsp_prop_include.as_str().to_string()));
+        }
+        if !self.order_by.is_empty() {
+            let mut order_by = Vec::new();
+            for (property, order) in &self.order_by {
+                validate_property(property)?;
+                order_by.push(format!("{property}|{}", order.as_str()));
+            }
+            options.push(("order-by", order_by.join(",")));
+        }
+
+        Ok(options)
+    }
+}
+
+/// Class names are camel case identifiers with a package prefix, e.g. `fvBD`.
+fn validate_class(class: &str) -> Result<(), QueryError> {
+    let mut chars = class.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_alphanumeric());
+    if !valid {
+        return Err(QueryError::InvalidClass(class.to_string()));
+    }
+    Ok(())
+}
+
+/// Properties are referenced as `class.property`, e.g. `fvTenant.name`.
+pub(crate) fn validate_property(property: &str) -> Result<(), QueryError> {
+    let invalid = || QueryError::InvalidProperty(property.to_string());
+    let (class, name) = property.split_once('.').ok_or_else(invalid)?;
+    validate_class(class).map_err(|_| invalid())?;
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(invalid());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn class_query() {
+        let uri = Query::class("fvAEPg").build().unwrap();
+        assert_eq!(uri, "class/fvAEPg.json");
+    }
+
+    #[test]
+    fn mo_query() {
+        let uri = Query::mo("uni/tn-common").build().unwrap();
+        assert_eq!(uri, "mo/uni/tn-common.json");
+    }
+
+    #[test]
+    fn mo_query_with_brackets_and_spaces() {
+        let uri = Query::mo("topology/pod-1/paths-101/pathep-[eth1/1]")
+            .build()
+            .unwrap();
+        assert_eq!(uri, "mo/topology/pod-1/paths-101/pathep-[eth1/1].json");
+
+        let uri = Query::mo("uni/tn-a b#c").build().unwrap();
+        assert_eq!(uri, "mo/uni/tn-a%20b%23c.json");
+    }
+
+    #[test]
+    fn all_options() {
+        let uri = Query::class("fvTenant")
+            .query_target(QueryTarget::Subtree)
+            .target_subtree_class("fvBD")
+            .target_subtree_class("fvCtx")
+            .rsp_subtree(RspSubtree::Full)
+            .rsp_subtree_class("fvSubnet")
+            .rsp_subtree_include(RspSubtreeInclude::Faults)
+            .rsp_subtree_include(RspSubtreeInclude::Health)
+            .rsp_prop_include(RspPropInclude::ConfigOnly)
+            .order_by("fvBD.name", Order::Desc)
+            .build()
+            .unwrap();
+        assert_eq!(
+            uri,
+            "class/fvTenant.json?query-target=subtree&target-subtree-class=fvBD,fvCtx\
+             &rsp-subtree=full&rsp-subtree-class=fvSubnet&rsp-subtree-include=faults,health\
+             &rsp-prop-include=config-only&order-by=fvBD.name|desc"
+        );
+    }
+
+    #[test]
+    fn count_query() {
+        let uri = Query::class("fvCEp")
+            .rsp_subtree_include(RspSubtreeInclude::Count)
+            .build()
+            .unwrap();
+        assert_eq!(uri, "class/fvCEp.json?rsp-subtree-include=count");
+    }
+
+    #[test]
+    fn filters() {
+        let uri = Query::class("fvTenant")
+            .query_target_filter(Filter::and([
+                Filter::eq("fvTenant.name", "x y"),
+                Filter::wcard("fvTenant.dn", "a&b=c"),
+            ]))
+            .rsp_subtree(RspSubtree::Children)
+            .rsp_subtree_filter(Filter::ne("fvBD.name", "default"))
+            .build()
+            .unwrap();
+        assert_eq!(
+            uri,
+            "class/fvTenant.json?query-target-filter=and(eq(fvTenant.name,%22x%20y%22),\
+             wcard(fvTenant.dn,%22a%26b%3Dc%22))&rsp-subtree=children\
+             &rsp-subtree-filter=ne(fvBD.name,%22default%22)"
+        );
+    }
+
+    #[test]
+    fn invalid_filters() {
+        let result = Query::class("fvTenant")
+            .query_target_filter(Filter::eq("name", "x"))
+            .build();
+        assert_eq!(
+            result,
+            Err(QueryError::InvalidProperty(String::from("name")))
+        );
+
+        let result = Query::class("fvTenant")
+            .rsp_subtree_filter(Filter::eq("fvBD.name", "x"))
+            .build();
+        assert!(matches!(
+            result,
+            Err(QueryError::InvalidCombination("rsp-subtree-filter", _))
+        ));
+    }
+
+    #[test]
+    fn invalid_class() {
+        for class in ["", "FvTenant", "fv Tenant", "fvTenant.json"] {
+            assert_eq!(
+                Query::class(class).build(),
+                Err(QueryError::InvalidClass(class.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_dn() {
+        for dn in ["", "/uni/tn-common", "uni/"] {
+            assert_eq!(
+                Query::mo(dn).build(),
+                Err(QueryError::InvalidDn(dn.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn target_subtree_class_without_query_target() {
+        let result = Query::class("fvTenant")
+            .target_subtree_class("fvBD")
+            .build();
+        assert!(matches!(
+            result,
+            Err(QueryError::InvalidCombination("target-subtree-class", _))
+        ));
+
+        let result = Query::class("fvTenant")
+            .query_target(QueryTarget::SelfOnly)
+            .target_subtree_class("fvBD")
+            .build();
+        assert!(matches!(
+            result,
+            Err(QueryError::InvalidCombination("target-subtree-class", _))
+        ));
+    }
+
+    #[test]
+    fn rsp_subtree_class_without_rsp_subtree() {
+        let result = Query::class("fvTenant")
+            .rsp_subtree(RspSubtree::No)
+            .rsp_subtree_class("fvBD")
+            .build();
+        assert!(matches!(
+            result,
+            Err(QueryError::InvalidCombination("rsp-subtree-class", _))
+        ));
+    }
+
+    #[test]
+    fn invalid_order_by() {
+        for property in ["name", "fvTenant.", "fvTenant.na me", ".name"] {
+            let result = Query::class("fvTenant")
+                .order_by(property, Order::Asc)
+                .build();
+            assert_eq!(
+                result,
+                Err(QueryError::InvalidProperty(property.to_string()))
+            );
+        }
+    }
+}
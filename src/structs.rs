@@ -0,0 +1,231 @@
+//! Envelopes of the JSON returned by the APIC, for reading objects into
+//! plain serde structs without `aci_struct!` or the derive.
+//!
+//! ```
+//! use rustyaci::structs::{ClassWrapper, ImData};
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Tenant {
+//!     dn: String,
+//!     name: String,
+//! }
+//!
+//! let data = r#"{"totalCount": "1", "imdata": [
+//!     {"fvTenant": {"attributes": {"dn": "uni/tn-A", "name": "A"}}}
+//! ]}"#;
+//! let response: ImData<ClassWrapper<Tenant>> = serde_json::from_str(data).unwrap();
+//!
+//! assert_eq!(1, response.total_count);
+//! assert_eq!("fvTenant", response.imdata[0].class_name);
+//! assert_eq!("A", response.imdata[0].attributes.name);
+//! ```
+
+use std::fmt;
+
+use serde::{
+    de::{self, IgnoredAny, MapAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// The body of a response, `{"totalCount": "..", "imdata": [..]}`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ImData<T> {
+    /// The number of matching objects, which can be more than the objects in
+    /// `imdata` for paginated queries.
+    #[serde(
+        rename = "totalCount",
+        default,
+        deserialize_with = "count_from_str",
+        serialize_with = "count_to_str"
+    )]
+    pub total_count: u64,
+    pub imdata: Vec<T>,
+}
+
+/// Reads `totalCount`, which the APIC sends as a string, but also accepts a
+/// number.
+fn count_from_str<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    struct CountVisitor;
+
+    impl Visitor<'_> for CountVisitor {
+        type Value = u64;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a count as string or number")
+        }
+
+        fn visit_u64<E: de::Error>(self, count: u64) -> Result<u64, E> {
+            Ok(count)
+        }
+
+        fn visit_i64<E: de::Error>(self, count: i64) -> Result<u64, E> {
+            u64::try_from(count).map_err(E::custom)
+        }
+
+        fn visit_str<E: de::Error>(self, count: &str) -> Result<u64, E> {
+            count.parse().map_err(E::custom)
+        }
+    }
+
+    deserializer.deserialize_any(CountVisitor)
+}
+
+/// Like `count_from_str`, for responses where a missing `totalCount` must be
+/// told apart from zero.
+pub(crate) fn optional_count<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<u64>, D::Error> {
+    count_from_str(deserializer).map(Some)
+}
+
+fn count_to_str<S: Serializer>(count: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&count.to_string())
+}
+
+/// An object of any class, `{"<class>": {"attributes": {..}}}`, with its
+/// attributes read into `T`. Children are ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassWrapper<T> {
+    pub class_name: String,
+    pub attributes: T,
+}
+
+#[derive(Deserialize, Serialize)]
+struct Attributes<T> {
+    attributes: T,
+}
+
+impl<T: Serialize> Serialize for ClassWrapper<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(
+            &self.class_name,
+            &Attributes {
+                attributes: &self.attributes,
+            },
+        )?;
+        map.end()
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for ClassWrapper<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(ClassWrapperVisitor(std::marker::PhantomData))
+    }
+}
+
+struct ClassWrapperVisitor<T>(std::marker::PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for ClassWrapperVisitor<T> {
+    type Value = ClassWrapper<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an object with the class name as its only key")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let Some((class_name, body)) = map.next_entry::<String, Attributes<T>>()? else {
+            return Err(de::Error::invalid_length(0, &self));
+        };
+        if map.next_key::<IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(2, &self));
+        }
+
+        Ok(ClassWrapper {
+            class_name,
+            attributes: body.attributes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use serde_json::Value;
+
+    use super::*;
+
+    #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+    struct Tenant {
+        dn: String,
+        name: String,
+    }
+
+    #[test]
+    fn parse() {
+        let data = fs::read_to_string("tests/json/fvTenant.json").unwrap();
+        let response = serde_json::from_str::<ImData<ClassWrapper<Tenant>>>(&data).unwrap();
+
+        assert_eq!(2, response.total_count);
+        let tenants = response
+            .imdata
+            .iter()
+            .map(|tenant| tenant.attributes.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                Tenant {
+                    dn: String::from("uni/tn-infra"),
+                    name: String::from("infra")
+                },
+                Tenant {
+                    dn: String::from("uni/tn-common"),
+                    name: String::from("common")
+                },
+            ],
+            tenants
+        );
+        assert!(response
+            .imdata
+            .iter()
+            .all(|tenant| tenant.class_name == "fvTenant"));
+    }
+
+    #[test]
+    fn parse_numeric_count() {
+        let response =
+            serde_json::from_str::<ImData<Value>>(r#"{"totalCount": 3, "imdata": []}"#).unwrap();
+
+        assert_eq!(3, response.total_count);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(
+            serde_json::from_str::<ImData<Value>>(r#"{"totalCount": "x", "imdata": []}"#).is_err()
+        );
+        assert!(
+            serde_json::from_str::<ImData<Value>>(r#"{"totalCount": -1, "imdata": []}"#).is_err()
+        );
+        assert!(serde_json::from_str::<ClassWrapper<Value>>("{}").is_err());
+        assert!(serde_json::from_str::<ClassWrapper<Value>>(
+            r#"{"fvTenant": {"attributes": {}}, "fvBD": {"attributes": {}}}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn serialize() {
+        let response = ImData {
+            total_count: 1,
+            imdata: vec![ClassWrapper {
+                class_name: String::from("fvTenant"),
+                attributes: Tenant {
+                    dn: String::from("uni/tn-A"),
+                    name: String::from("A"),
+                },
+            }],
+        };
+
+        assert_eq!(
+            serde_json::json!({
+                "totalCount": "1",
+                "imdata": [{"fvTenant": {"attributes": {"dn": "uni/tn-A", "name": "A"}}}]
+            }),
+            serde_json::to_value(&response).unwrap()
+        );
+    }
+}
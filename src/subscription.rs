@@ -0,0 +1,277 @@
+use std::{collections::VecDeque, sync::atomic::Ordering, time::Duration};
+
+use futures::{stream, SinkExt, Stream, StreamExt};
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use tokio::{net::TcpStream, time};
+use tokio_tungstenite::{tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+use crate::{with_option, AciError, Executor, ACI};
+
+/// The APIC drops subscriptions that are not refreshed within 90 seconds.
+pub const SUBSCRIPTION_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Kind of change reported for a managed object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeStatus {
+    Created,
+    Modified,
+    Deleted,
+}
+
+impl ChangeStatus {
+    fn from_status(status: &str) -> Self {
+        if status.contains("deleted") {
+            ChangeStatus::Deleted
+        } else if status.contains("created") {
+            ChangeStatus::Created
+        } else {
+            ChangeStatus::Modified
+        }
+    }
+}
+
+/// A change of a managed object pushed by the APIC for a subscription.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangeEvent {
+    pub subscription_ids: Vec<String>,
+    pub class_name: String,
+    pub status: ChangeStatus,
+    pub attributes: Map<String, Value>,
+}
+
+impl ChangeEvent {
+    pub fn dn(&self) -> Option<&str> {
+        self.attributes.get("dn").and_then(Value::as_str)
+    }
+
+    /// Deserializes the changed object, e.g. into a struct created with
+    /// [`aci_struct!`](crate::aci_struct).
+    pub fn object<T>(&self) -> Result<T, serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        serde_json::from_value(serde_json::json!({
+            &self.class_name: { "attributes": self.attributes }
+        }))
+    }
+}
+
+/// Parses a message of the event channel into the changes it contains.
+fn parse_events(message: &str) -> Result<Vec<ChangeEvent>, AciError> {
+    let message: Value = serde_json::from_str(message)?;
+    let subscription_ids = match &message["subscriptionId"] {
+        Value::Array(ids) => ids
+            .iter()
+            .filter_map(|id| id.as_str().map(String::from))
+            .collect(),
+        Value::String(id) => vec![id.clone()],
+        _ => Vec::new(),
+    };
+
+    let Some(imdata) = message["imdata"].as_array() else {
+        return Ok(Vec::new());
+    };
+    let events = imdata
+        .iter()
+        .filter_map(Value::as_object)
+        .flat_map(|object| object.iter())
+        .map(|(class_name, object)| {
+            let attributes = object["attributes"]
+                .as_object()
+                .cloned()
+                .unwrap_or_default();
+            let status = attributes
+                .get("status")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            ChangeEvent {
+                subscription_ids: subscription_ids.clone(),
+                class_name: class_name.clone(),
+                status: ChangeStatus::from_status(status),
+                attributes,
+            }
+        })
+        .collect();
+
+    Ok(events)
+}
+
+struct State {
+    server: usize,
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    subscription_id: String,
+    refresh: time::Interval,
+    pending: VecDeque<ChangeEvent>,
+}
+
+impl<E: Executor> ACI<E> {
+    /// Subscribes to the objects of a class or MO query. The changes are
+    /// pushed by the APIC over the event channel `wss://<apic>/socket<token>`
+    /// and the subscription is refreshed every
+    /// [`SUBSCRIPTION_REFRESH_INTERVAL`] while the stream is polled.
+    ///
+    /// A subscription only exists on the controller holding the channel, so
+    /// its requests are not failed over. Once another request fails over to
+    /// the next controller, the stream ends with [`AciError::ServerChanged`].
+    ///
+    /// The channel is opened with the session token, so subscriptions need a
+    /// password login. With signature based authentication this fails with
+    /// [`AciError::NoSessionToken`].
+    pub async fn subscribe(
+        &self,
+        uri: String,
+    ) -> Result<impl Stream<Item = Result<ChangeEvent, AciError>> + '_, AciError> {
+        self.ensure_session().await?;
+        let token = self.get_token();
+        if token.is_empty() {
+            return Err(AciError::NoSessionToken);
+        }
+        let server = self.active.load(Ordering::SeqCst);
+        let socket_url = format!("wss://{}/socket{token}", self.servers[server]);
+        self.subscribe_to(server, &socket_url, uri, SUBSCRIPTION_REFRESH_INTERVAL)
+            .await
+    }
+
+    pub(crate) async fn subscribe_to(
+        &self,
+        server: usize,
+        socket_url: &str,
+        uri: String,
+        refresh_interval: Duration,
+    ) -> Result<impl Stream<Item = Result<ChangeEvent, AciError>> + '_, AciError> {
+        // The socket is opened first, so no event of the subscription is missed
+        let connect = tokio_tungstenite::connect_async_tls_with_config(
+            socket_url,
+            None,
+            false,
+            Some(self.config.connector()?),
+        );
+        let (socket, _) = match self.config.get_connect_timeout() {
+            Some(timeout) => time::timeout(timeout, connect)
+                .await
+                .map_err(anyhow::Error::from)??,
+            None => connect.await?,
+        };
+
+        self.ensure_server(server)?;
+        let reply = self
+            .send_to_active(Method::GET, &with_option(&uri, "subscription=yes"), None)
+            .await?;
+        let response: Value = serde_json::from_str(&reply.body)?;
+        let Some(subscription_id) = response["subscriptionId"].as_str() else {
+            return Err(AciError::UnexpectedResponse(format!(
+                "no subscriptionId in the response to {uri}: {}",
+                reply.body
+            )));
+        };
+
+        let state = State {
+            server,
+            socket,
+            subscription_id: subscription_id.to_string(),
+            refresh: time::interval_at(time::Instant::now() + refresh_interval, refresh_interval),
+            pending: VecDeque::new(),
+        };
+
+        Ok(stream::unfold(Some(state), move |state| async move {
+            let mut state = state?;
+            loop {
+                if let Some(event) = state.pending.pop_front() {
+                    return Some((Ok(event), Some(state)));
+                }
+
+                tokio::select! {
+                    _ = state.refresh.tick() => {
+                        let refresh = match self.ensure_server(state.server) {
+                            Ok(()) => self.refresh_subscription(&state.subscription_id).await,
+                            Err(error) => Err(error),
+                        };
+                        if let Err(error) = refresh {
+                            let _ = state.socket.close(None).await;
+                            return Some((Err(error), None));
+                        }
+                    }
+                    message = state.socket.next() => match message {
+                        Some(Ok(Message::Text(text))) => match parse_events(&text) {
+                            Ok(events) => state.pending.extend(events),
+                            Err(error) => return Some((Err(error), Some(state))),
+                        },
+                        Some(Ok(Message::Ping(payload))) => {
+                            let _ = state.socket.send(Message::Pong(payload)).await;
+                        }
+                        Some(Ok(Message::Close(_))) | None => return None,
+                        Some(Ok(_)) => {}
+                        Some(Err(error)) => return Some((Err(error.into()), None)),
+                    }
+                }
+            }
+        }))
+    }
+
+    /// Extends the lifetime of a subscription on the active APIC. The request
+    /// is not failed over, another controller doesn't know the subscription.
+    pub async fn refresh_subscription(&self, subscription_id: &str) -> Result<(), AciError> {
+        let uri = format!("subscriptionRefresh.json?id={subscription_id}");
+        self.send_to_active(Method::GET, &uri, None).await?;
+        Ok(())
+    }
+
+    /// Fails if the requests no longer go to the controller `server`, e.g.
+    /// after a fail over.
+    fn ensure_server(&self, server: usize) -> Result<(), AciError> {
+        let active = self.active.load(Ordering::SeqCst);
+        if active != server {
+            return Err(AciError::ServerChanged {
+                subscribed: self.servers[server].clone(),
+                active: self.servers[active].clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_from_message() {
+        let message = r#"{
+            "subscriptionId": ["72057594037927937"],
+            "imdata": [
+                {"fvTenant": {"attributes": {"dn": "uni/tn-a", "name": "a", "status": "created"}}},
+                {"fvBD": {"attributes": {"dn": "uni/tn-a/BD-b", "status": "modified"}}},
+                {"fvTenant": {"attributes": {"dn": "uni/tn-c", "status": "deleted"}}}
+            ]
+        }"#;
+
+        let events = parse_events(message).unwrap();
+
+        assert_eq!(3, events.len());
+        assert_eq!(vec!["72057594037927937"], events[0].subscription_ids);
+        assert_eq!("fvTenant", events[0].class_name);
+        assert_eq!(ChangeStatus::Created, events[0].status);
+        assert_eq!(Some("uni/tn-a"), events[0].dn());
+        assert_eq!(ChangeStatus::Modified, events[1].status);
+        assert_eq!(ChangeStatus::Deleted, events[2].status);
+    }
+
+    #[test]
+    fn event_object() {
+        #[derive(serde::Deserialize)]
+        struct Tenant {
+            #[serde(rename = "fvTenant")]
+            tenant: Value,
+        }
+
+        let message = r#"{"subscriptionId": "1", "imdata": [
+            {"fvTenant": {"attributes": {"name": "a", "status": "created"}}}
+        ]}"#;
+        let events = parse_events(message).unwrap();
+        let tenant: Tenant = events[0].object().unwrap();
+
+        assert_eq!("a", tenant.tenant["attributes"]["name"]);
+    }
+}
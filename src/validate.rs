@@ -0,0 +1,522 @@
+//! Checks objects against the class metadata before they are posted, so
+//! typos show up locally instead of as APIC errors, e.g.
+//!
+//! ```no_run
+//! use rustyaci::{meta::AciMeta, mo::ManagedObject};
+//!
+//! let meta = AciMeta::from_file("aci-meta.json").unwrap();
+//! let bd = ManagedObject::new("fvBD")
+//!     .dn("uni/tn-A/BD-BD1")
+//!     .attribute("name", "BD1")
+//!     .attribute("arpFlod", "yes");
+//! for error in meta.validate_mo(&bd) {
+//!     println!("{error}"); // $.fvBD.attributes.arpFlod: Unknown attribute ...
+//! }
+//! ```
+
+use serde_json::{Map, Value};
+
+use crate::{
+    dn::Dn,
+    meta::{AciMeta, ClassMeta},
+    mo::ManagedObject,
+};
+
+/// A problem of an object, with the JSON path of the offending value, e.g.
+/// `$.fvTenant.children[0].fvBD.attributes.arpFlood`.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("{path}: {kind}")]
+pub struct ValidationError {
+    pub path: String,
+    pub kind: ValidationErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ValidationErrorKind {
+    #[error("Expected an object with the class name as its only key")]
+    InvalidObject,
+    #[error("Unknown class {0:?}")]
+    UnknownClass(String),
+    #[error("Unknown attribute {attribute:?} of class {class:?}")]
+    UnknownAttribute { class: String, attribute: String },
+    #[error("Invalid value {value:?}, expected {}", expected(valid))]
+    InvalidValue { value: String, valid: Vec<String> },
+    #[error("{child:?} can't be a child of {parent:?}")]
+    InvalidContainment { parent: String, child: String },
+    #[error("Invalid dn {0:?}")]
+    InvalidDn(String),
+    #[error("The dn ends with {actual:?}, expected {expected:?}")]
+    RnMismatch { expected: String, actual: String },
+    #[error("The dn {dn:?} isn't below the dn of the parent {parent:?}")]
+    ParentMismatch { dn: String, parent: String },
+}
+
+fn expected(valid: &[String]) -> String {
+    if valid.is_empty() {
+        String::from("a string")
+    } else {
+        format!("one of {valid:?}")
+    }
+}
+
+/// Attributes every object accepts, even if the metadata lacks them.
+const COMMON_ATTRIBUTES: &[&str] = &["dn", "rn", "status"];
+
+impl AciMeta {
+    /// Checks a JSON body like `{"fvTenant": {"attributes": {..}, "children": [..]}}`
+    /// and returns all problems found, an empty list if it is valid.
+    pub fn validate(&self, object: &Value) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        self.validate_object(object, "$", None, &mut errors);
+        errors
+    }
+
+    /// Like [`AciMeta::validate`] for a [`ManagedObject`].
+    pub fn validate_mo(&self, mo: &ManagedObject) -> Vec<ValidationError> {
+        match serde_json::to_value(mo) {
+            Ok(object) => self.validate(&object),
+            Err(_) => vec![ValidationError {
+                path: String::from("$"),
+                kind: ValidationErrorKind::InvalidObject,
+            }],
+        }
+    }
+
+    fn validate_object(
+        &self,
+        object: &Value,
+        path: &str,
+        parent: Option<(&str, Option<Dn>)>,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let mut error = |path: String, kind| errors.push(ValidationError { path, kind });
+
+        let Some((class_name, body)) = object
+            .as_object()
+            .filter(|object| object.len() == 1)
+            .and_then(|object| object.iter().next())
+            .filter(|(_, body)| body.is_object())
+        else {
+            error(path.to_string(), ValidationErrorKind::InvalidObject);
+            return;
+        };
+        let path = format!("{path}.{class_name}");
+        let Some(class) = self.classes.get(class_name) else {
+            error(path, ValidationErrorKind::UnknownClass(class_name.clone()));
+            return;
+        };
+
+        if let Some((parent_class, _)) = &parent {
+            if !self.can_contain(parent_class, class_name) {
+                error(
+                    path.clone(),
+                    ValidationErrorKind::InvalidContainment {
+                        parent: parent_class.to_string(),
+                        child: class_name.clone(),
+                    },
+                );
+            }
+        }
+
+        let empty = Map::new();
+        let attributes = body["attributes"].as_object().unwrap_or(&empty);
+        for (name, value) in attributes {
+            let path = format!("{path}.attributes.{name}");
+            let Some(property) = class.properties.get(name) else {
+                if !COMMON_ATTRIBUTES.contains(&name.as_str()) {
+                    error(
+                        path,
+                        ValidationErrorKind::UnknownAttribute {
+                            class: class_name.clone(),
+                            attribute: name.clone(),
+                        },
+                    );
+                }
+                continue;
+            };
+            // Only enums and bitmasks are closed sets, other properties like
+            // the ports of `vzEntry` list named constants but take any value
+            let valid = match property.uitype.as_str() {
+                "enum" | "bitmask" => property
+                    .valid_values
+                    .iter()
+                    .map(|value| value.local_name.as_str())
+                    .filter(|value| !value.is_empty())
+                    .collect::<Vec<_>>(),
+                _ => Vec::new(),
+            };
+            // The APIC expects every attribute as a string, even numbers
+            let Some(value) = value.as_str() else {
+                error(
+                    path,
+                    ValidationErrorKind::InvalidValue {
+                        value: value.to_string(),
+                        valid: valid.iter().map(|value| value.to_string()).collect(),
+                    },
+                );
+                continue;
+            };
+            let is_valid = if property.uitype == "bitmask" {
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .all(|value| valid.contains(&value))
+            } else {
+                valid.contains(&value)
+            };
+            if !valid.is_empty() && !is_valid {
+                error(
+                    path,
+                    ValidationErrorKind::InvalidValue {
+                        value: value.to_string(),
+                        valid: valid.iter().map(|value| value.to_string()).collect(),
+                    },
+                );
+            }
+        }
+
+        let dn = match attributes.get("dn").and_then(Value::as_str) {
+            Some(dn) => {
+                let dn_path = format!("{path}.attributes.dn");
+                match dn.parse::<Dn>() {
+                    Ok(dn) => {
+                        if let Some(expected) = expected_rn(class, attributes) {
+                            if dn.rn().as_str() != expected {
+                                error(
+                                    dn_path.clone(),
+                                    ValidationErrorKind::RnMismatch {
+                                        expected,
+                                        actual: dn.rn().to_string(),
+                                    },
+                                );
+                            }
+                        }
+                        if let Some((_, Some(parent_dn))) = &parent {
+                            if dn.parent().as_ref() != Some(parent_dn) {
+                                error(
+                                    dn_path,
+                                    ValidationErrorKind::ParentMismatch {
+                                        dn: dn.to_string(),
+                                        parent: parent_dn.to_string(),
+                                    },
+                                );
+                            }
+                        }
+                        Some(dn)
+                    }
+                    Err(_) => {
+                        error(dn_path, ValidationErrorKind::InvalidDn(dn.to_string()));
+                        None
+                    }
+                }
+            }
+            // Without a dn of their own, children are named below the parent
+            None => parent
+                .as_ref()
+                .and_then(|(_, parent_dn)| parent_dn.clone())
+                .zip(expected_rn(class, attributes))
+                .and_then(|(parent_dn, rn)| rn.parse().ok().map(|rn| parent_dn.child(rn))),
+        };
+
+        if let Some(children) = body.get("children") {
+            let Some(children) = children.as_array() else {
+                errors.push(ValidationError {
+                    path: format!("{path}.children"),
+                    kind: ValidationErrorKind::InvalidObject,
+                });
+                return;
+            };
+            for (index, child) in children.iter().enumerate() {
+                self.validate_object(
+                    child,
+                    &format!("{path}.children[{index}]"),
+                    Some((class_name, dn.clone())),
+                    errors,
+                );
+            }
+        }
+    }
+
+    fn can_contain(&self, parent: &str, child: &str) -> bool {
+        let contains = self
+            .classes
+            .get(parent)
+            .is_some_and(|parent| parent.contains.contains(child));
+        let contained_by = self
+            .classes
+            .get(child)
+            .is_some_and(|child| child.contained_by.contains(parent));
+        contains || contained_by
+    }
+}
+
+/// The RN built from the RN format and the naming attributes, `None` if one
+/// of them is missing.
+fn expected_rn(class: &ClassMeta, attributes: &Map<String, Value>) -> Option<String> {
+    if class.rn_format.is_empty() {
+        return None;
+    }
+    let mut rn = class.rn_format.clone();
+    for name in class.naming_properties() {
+        let value = attributes.get(name)?.as_str()?;
+        rn = rn.replacen(&format!("{{{name}}}"), value, 1);
+    }
+    Some(rn)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn meta() -> AciMeta {
+        AciMeta::from_file("tests/json/meta/aci-meta.json").unwrap()
+    }
+
+    fn kinds(errors: Vec<ValidationError>) -> Vec<(String, ValidationErrorKind)> {
+        errors
+            .into_iter()
+            .map(|error| (error.path, error.kind))
+            .collect()
+    }
+
+    #[test]
+    fn valid() {
+        let tenant = json!({
+            "fvTenant": {
+                "attributes": {"dn": "uni/tn-A", "name": "A", "status": "created,modified"},
+                "children": [
+                    {"fvBD": {
+                        "attributes": {"name": "BD1", "arpFlood": "yes", "unkMacUcastAct": "flood"},
+                        "children": [
+                            {"fvSubnet": {"attributes": {"ip": "10.0.0.1/24", "scope": "public,shared"}}},
+                            {"fvRsCtx": {"attributes": {"tnFvCtxName": "VRF1"}}}
+                        ]
+                    }},
+                    {"fvCtx": {"attributes": {"dn": "uni/tn-A/ctx-VRF1", "name": "VRF1", "pcEnfPref": "enforced"}}}
+                ]
+            }
+        });
+
+        assert_eq!(Vec::<ValidationError>::new(), meta().validate(&tenant));
+    }
+
+    #[test]
+    fn unknown_class_and_attribute() {
+        let tenant = json!({
+            "fvTenant": {
+                "attributes": {"name": "A", "descrption": "typo"},
+                "children": [{"fvBridgeDomain": {"attributes": {"name": "BD1"}}}]
+            }
+        });
+
+        assert_eq!(
+            vec![
+                (
+                    String::from("$.fvTenant.attributes.descrption"),
+                    ValidationErrorKind::UnknownAttribute {
+                        class: String::from("fvTenant"),
+                        attribute: String::from("descrption"),
+                    }
+                ),
+                (
+                    String::from("$.fvTenant.children[0].fvBridgeDomain"),
+                    ValidationErrorKind::UnknownClass(String::from("fvBridgeDomain"))
+                ),
+            ],
+            kinds(meta().validate(&tenant))
+        );
+    }
+
+    #[test]
+    fn invalid_values() {
+        let bd = json!({
+            "fvBD": {
+                "attributes": {"name": "BD1", "unkMacUcastAct": "drop"},
+                "children": [{"fvSubnet": {"attributes": {"ip": "10.0.0.1/24", "scope": "public,global"}}}]
+            }
+        });
+
+        let errors = kinds(meta().validate(&bd));
+        assert_eq!(2, errors.len());
+        assert_eq!(
+            (
+                String::from("$.fvBD.attributes.unkMacUcastAct"),
+                ValidationErrorKind::InvalidValue {
+                    value: String::from("drop"),
+                    valid: vec![String::from("proxy"), String::from("flood")],
+                }
+            ),
+            errors[0]
+        );
+        assert_eq!("$.fvBD.children[0].fvSubnet.attributes.scope", errors[1].0);
+    }
+
+    #[test]
+    fn non_string_values() {
+        let bd = json!({
+            "fvBD": {"attributes": {"name": "BD1", "unkMacUcastAct": 1, "descr": null}}
+        });
+
+        assert_eq!(
+            vec![
+                (
+                    String::from("$.fvBD.attributes.descr"),
+                    ValidationErrorKind::InvalidValue {
+                        value: String::from("null"),
+                        valid: Vec::new(),
+                    }
+                ),
+                (
+                    String::from("$.fvBD.attributes.unkMacUcastAct"),
+                    ValidationErrorKind::InvalidValue {
+                        value: String::from("1"),
+                        valid: vec![String::from("proxy"), String::from("flood")],
+                    }
+                ),
+            ],
+            kinds(meta().validate(&bd))
+        );
+    }
+
+    #[test]
+    fn open_values() {
+        let entry = json!({
+            "vzFilter": {
+                "attributes": {"dn": "uni/tn-A/flt-web", "name": "web"},
+                "children": [
+                    {"vzEntry": {"attributes": {"name": "alt", "etherT": "ip", "dFromPort": "8080"}}},
+                    {"vzEntry": {"attributes": {"name": "https", "etherT": "ip", "dFromPort": "https"}}},
+                    {"vzEntry": {"attributes": {"name": "bad", "etherT": "ipv9", "dFromPort": 8080}}}
+                ]
+            }
+        });
+
+        assert_eq!(
+            vec![
+                (
+                    String::from("$.vzFilter.children[2].vzEntry.attributes.dFromPort"),
+                    ValidationErrorKind::InvalidValue {
+                        value: String::from("8080"),
+                        valid: Vec::new(),
+                    }
+                ),
+                (
+                    String::from("$.vzFilter.children[2].vzEntry.attributes.etherT"),
+                    ValidationErrorKind::InvalidValue {
+                        value: String::from("ipv9"),
+                        valid: vec![
+                            String::from("unspecified"),
+                            String::from("ip"),
+                            String::from("arp")
+                        ],
+                    }
+                ),
+            ],
+            kinds(meta().validate(&entry))
+        );
+    }
+
+    #[test]
+    fn invalid_containment() {
+        let tenant = json!({
+            "fvTenant": {
+                "attributes": {"name": "A"},
+                "children": [{"fvSubnet": {"attributes": {"ip": "10.0.0.1/24"}}}]
+            }
+        });
+
+        assert_eq!(
+            vec![(
+                String::from("$.fvTenant.children[0].fvSubnet"),
+                ValidationErrorKind::InvalidContainment {
+                    parent: String::from("fvTenant"),
+                    child: String::from("fvSubnet"),
+                }
+            )],
+            kinds(meta().validate(&tenant))
+        );
+    }
+
+    #[test]
+    fn dn_mismatch() {
+        let tenant = json!({
+            "fvTenant": {
+                "attributes": {"dn": "uni/tn-A", "name": "B"},
+                "children": [
+                    {"fvBD": {"attributes": {"dn": "uni/tn-C/BD-BD1", "name": "BD1"}}},
+                    {"fvBD": {
+                        "attributes": {"name": "BD2"},
+                        "children": [{"fvSubnet": {"attributes": {"dn": "uni/tn-A/BD-BD2/subnet-[10.0.0.1/24]", "ip": "10.0.0.1/24"}}}]
+                    }},
+                    {"fvCtx": {"attributes": {"dn": "uni/tn-A/ctx-[VRF1", "name": "VRF1"}}}
+                ]
+            }
+        });
+
+        let errors = kinds(meta().validate(&tenant));
+        assert_eq!(
+            vec![
+                (
+                    String::from("$.fvTenant.attributes.dn"),
+                    ValidationErrorKind::RnMismatch {
+                        expected: String::from("tn-B"),
+                        actual: String::from("tn-A"),
+                    }
+                ),
+                (
+                    String::from("$.fvTenant.children[0].fvBD.attributes.dn"),
+                    ValidationErrorKind::ParentMismatch {
+                        dn: String::from("uni/tn-C/BD-BD1"),
+                        parent: String::from("uni/tn-A"),
+                    }
+                ),
+                (
+                    String::from("$.fvTenant.children[2].fvCtx.attributes.dn"),
+                    ValidationErrorKind::InvalidDn(String::from("uni/tn-A/ctx-[VRF1"))
+                ),
+            ],
+            errors
+        );
+    }
+
+    #[test]
+    fn invalid_object() {
+        let meta = meta();
+
+        assert_eq!(
+            vec![(String::from("$"), ValidationErrorKind::InvalidObject)],
+            kinds(meta.validate(&json!({"fvTenant": {}, "fvBD": {}})))
+        );
+        assert_eq!(
+            vec![(
+                String::from("$.fvTenant.children"),
+                ValidationErrorKind::InvalidObject
+            )],
+            kinds(
+                meta.validate(&json!({"fvTenant": {"attributes": {"name": "A"}, "children": {}}}))
+            )
+        );
+    }
+
+    #[test]
+    fn validate_mo() {
+        let tenant = ManagedObject::new("fvTenant")
+            .dn("uni/tn-A")
+            .attribute("name", "A")
+            .child(
+                ManagedObject::new("fvCtx")
+                    .attribute("name", "VRF1")
+                    .attribute("pcEnfPref", "on"),
+            );
+
+        let errors = meta().validate_mo(&tenant);
+        assert_eq!(1, errors.len());
+        assert_eq!(
+            "$.fvTenant.children[0].fvCtx.attributes.pcEnfPref: Invalid value \"on\", expected one of [\"enforced\", \"unenforced\"]",
+            errors[0].to_string()
+        );
+    }
+}
@@ -0,0 +1,212 @@
+// Generated by rustyaci-gen from aci-meta.json, do not edit.
+
+use rustyaci::{dn::Dn, AciObject};
+
+rustyaci::aci_enum!(
+    /// Valid values of `fvBD.arpFlood`.
+    FvBDArpFlood {
+        No = "no",
+        Yes = "yes",
+        _ => Other,
+    }
+);
+
+rustyaci::aci_enum!(
+    /// Valid values of `fvBD.type`.
+    FvBDType {
+        Regular = "regular",
+        Fc = "fc",
+        _ => Other,
+    }
+);
+
+rustyaci::aci_enum!(
+    /// Valid values of `fvBD.unkMacUcastAct`.
+    FvBDUnkMacUcastAct {
+        Proxy = "proxy",
+        Flood = "flood",
+        _ => Other,
+    }
+);
+
+/// Bridge Domain (`fvBD`)
+#[derive(Debug, Clone, Default, PartialEq, AciObject)]
+#[aci(class = "fvBD")]
+pub struct FvBD {
+    pub dn: Option<Dn>,
+    /// Name
+    pub name: String,
+    /// ARP Flooding
+    #[aci(rename = "arpFlood")]
+    pub arp_flood: Option<FvBDArpFlood>,
+    /// Description
+    pub descr: Option<String>,
+    /// MAC Address
+    pub mac: Option<String>,
+    /// Name Alias
+    #[aci(rename = "nameAlias")]
+    pub name_alias: Option<String>,
+    /// Type
+    #[aci(rename = "type")]
+    pub type_: Option<FvBDType>,
+    /// L2 Unknown Unicast
+    #[aci(rename = "unkMacUcastAct")]
+    pub unk_mac_ucast_act: Option<FvBDUnkMacUcastAct>,
+    #[aci(children)]
+    pub fv_rs_ctx: Vec<FvRsCtx>,
+    #[aci(children)]
+    pub fv_subnet: Vec<FvSubnet>,
+}
+
+impl FvBD {
+    /// The format of the relative name.
+    pub const RN_FORMAT: &'static str = "BD-{name}";
+    /// The classes of the possible parents.
+    pub const CONTAINED_BY: &'static [&'static str] = &["fvTenant"];
+    /// The classes of the possible children.
+    pub const CONTAINS: &'static [&'static str] = &["fvRsCtx", "fvSubnet"];
+
+    /// The relative name, see [`Self::RN_FORMAT`].
+    pub fn rn(&self) -> String {
+        format!("BD-{}", self.name)
+    }
+}
+
+rustyaci::aci_enum!(
+    /// Valid values of `fvCtx.pcEnfDir`.
+    FvCtxPcEnfDir {
+        Ingress = "ingress",
+        Egress = "egress",
+        _ => Other,
+    }
+);
+
+rustyaci::aci_enum!(
+    /// Valid values of `fvCtx.pcEnfPref`.
+    FvCtxPcEnfPref {
+        Enforced = "enforced",
+        Unenforced = "unenforced",
+        _ => Other,
+    }
+);
+
+/// VRF (`fvCtx`)
+#[derive(Debug, Clone, Default, PartialEq, AciObject)]
+#[aci(class = "fvCtx")]
+pub struct FvCtx {
+    pub dn: Option<Dn>,
+    /// Name
+    pub name: String,
+    /// Description
+    pub descr: Option<String>,
+    /// Name Alias
+    #[aci(rename = "nameAlias")]
+    pub name_alias: Option<String>,
+    /// Policy Control Enforcement Direction
+    #[aci(rename = "pcEnfDir")]
+    pub pc_enf_dir: Option<FvCtxPcEnfDir>,
+    /// Policy Control Enforcement Preference
+    #[aci(rename = "pcEnfPref")]
+    pub pc_enf_pref: Option<FvCtxPcEnfPref>,
+}
+
+impl FvCtx {
+    /// The format of the relative name.
+    pub const RN_FORMAT: &'static str = "ctx-{name}";
+    /// The classes of the possible parents.
+    pub const CONTAINED_BY: &'static [&'static str] = &["fvTenant"];
+    /// The classes of the possible children.
+    pub const CONTAINS: &'static [&'static str] = &[];
+
+    /// The relative name, see [`Self::RN_FORMAT`].
+    pub fn rn(&self) -> String {
+        format!("ctx-{}", self.name)
+    }
+}
+
+/// Private Network (`fvRsCtx`)
+#[derive(Debug, Clone, Default, PartialEq, AciObject)]
+#[aci(class = "fvRsCtx")]
+pub struct FvRsCtx {
+    pub dn: Option<Dn>,
+    /// Private Network Name
+    #[aci(rename = "tnFvCtxName")]
+    pub tn_fv_ctx_name: Option<String>,
+}
+
+impl FvRsCtx {
+    /// The format of the relative name.
+    pub const RN_FORMAT: &'static str = "rsctx";
+    /// The classes of the possible parents.
+    pub const CONTAINED_BY: &'static [&'static str] = &["fvBD"];
+    /// The classes of the possible children.
+    pub const CONTAINS: &'static [&'static str] = &[];
+    /// The class the relation points to.
+    pub const RELATION_TARGET: &'static str = "fvCtx";
+
+    /// The relative name, see [`Self::RN_FORMAT`].
+    pub fn rn(&self) -> String {
+        String::from("rsctx")
+    }
+}
+
+/// Subnet (`fvSubnet`)
+#[derive(Debug, Clone, Default, PartialEq, AciObject)]
+#[aci(class = "fvSubnet")]
+pub struct FvSubnet {
+    pub dn: Option<Dn>,
+    /// Default Gateway IP
+    pub ip: String,
+    /// Subnet Control
+    pub ctrl: Option<Vec<String>>,
+    /// Description
+    pub descr: Option<String>,
+    /// Scope
+    pub scope: Option<Vec<String>>,
+}
+
+impl FvSubnet {
+    /// The format of the relative name.
+    pub const RN_FORMAT: &'static str = "subnet-[{ip}]";
+    /// The classes of the possible parents.
+    pub const CONTAINED_BY: &'static [&'static str] = &["fvAEPg", "fvBD"];
+    /// The classes of the possible children.
+    pub const CONTAINS: &'static [&'static str] = &[];
+
+    /// The relative name, see [`Self::RN_FORMAT`].
+    pub fn rn(&self) -> String {
+        format!("subnet-[{}]", self.ip)
+    }
+}
+
+/// Tenant (`fvTenant`)
+#[derive(Debug, Clone, Default, PartialEq, AciObject)]
+#[aci(class = "fvTenant")]
+pub struct FvTenant {
+    pub dn: Option<Dn>,
+    /// Name
+    pub name: String,
+    /// Description
+    pub descr: Option<String>,
+    /// Name Alias
+    #[aci(rename = "nameAlias")]
+    pub name_alias: Option<String>,
+    #[aci(children)]
+    pub fv_bd: Vec<FvBD>,
+    #[aci(children)]
+    pub fv_ctx: Vec<FvCtx>,
+}
+
+impl FvTenant {
+    /// The format of the relative name.
+    pub const RN_FORMAT: &'static str = "tn-{name}";
+    /// The classes of the possible parents.
+    pub const CONTAINED_BY: &'static [&'static str] = &["polUni"];
+    /// The classes of the possible children.
+    pub const CONTAINS: &'static [&'static str] = &["fvAp", "fvBD", "fvCtx", "vzFilter"];
+
+    /// The relative name, see [`Self::RN_FORMAT`].
+    pub fn rn(&self) -> String {
+        format!("tn-{}", self.name)
+    }
+}